@@ -0,0 +1,298 @@
+//! Small general purpose algorithms over slices.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::fenwick::Fenwick;
+
+/// Element occurring more than `a.len() / 2` times, found by Boyer-Moore voting and verified by counting.
+pub fn majority<T: Eq>(a: &[T]) -> Option<&T> {
+    let mut candidate = None;
+    let mut votes = 0usize;
+    for x in a {
+        if votes == 0 {
+            candidate = Some(x);
+            votes = 1;
+        } else if candidate == Some(x) {
+            votes += 1;
+        } else {
+            votes -= 1;
+        }
+    }
+    let candidate = candidate?;
+    let count = a.iter().filter(|&x| x == candidate).count();
+    (count * 2 > a.len()).then_some(candidate)
+}
+
+/// Elements occurring more than `a.len() / k` times, in order of first occurrence.
+/// Misra-Gries keeps at most `k - 1` candidates, which a second pass verifies.
+pub fn frequent_elements<T: Eq + Hash>(a: &[T], k: usize) -> Vec<&T> {
+    assert!(k >= 2, "k must be at least 2");
+    let mut counters: HashMap<&T, usize> = HashMap::with_capacity(k);
+    for x in a {
+        if let Some(c) = counters.get_mut(x) {
+            *c += 1;
+        } else if counters.len() < k - 1 {
+            counters.insert(x, 1);
+        } else {
+            counters.retain(|_, c| {
+                *c -= 1;
+                *c > 0
+            });
+        }
+    }
+
+    let mut counts: HashMap<&T, usize> = counters.into_keys().map(|x| (x, 0)).collect();
+    for x in a {
+        if let Some(c) = counts.get_mut(x) {
+            *c += 1;
+        }
+    }
+    let mut result = Vec::new();
+    for x in a {
+        if let Some(c) = counts.remove(x) {
+            if c * k > a.len() {
+                result.push(x);
+            }
+        }
+    }
+    result
+}
+
+/// Number of occurrences of each value.
+pub fn counts<T: Eq + Hash + Clone>(a: &[T]) -> HashMap<T, usize> {
+    let mut counts = HashMap::new();
+    for x in a {
+        *counts.entry(x.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Most frequent value and its count, the first to occur among ties.
+pub fn mode<T: Eq + Hash + Clone>(a: &[T]) -> Option<(T, usize)> {
+    let counts = counts(a);
+    let mut best: Option<(&T, usize)> = None;
+    for x in a {
+        let c = counts[x];
+        if best.map_or(true, |(_, b)| c > b) {
+            best = Some((x, c));
+        }
+    }
+    best.map(|(x, c)| (x.clone(), c))
+}
+
+/// Values occurring more than once, each reported once in order of their second occurrence.
+pub fn duplicates<T: Eq + Hash + Clone>(a: &[T]) -> Vec<T> {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    a.iter()
+        .filter(|&x| !seen.insert(x) && reported.insert(x))
+        .cloned()
+        .collect()
+}
+
+pub fn has_duplicates<T: Eq + Hash>(a: &[T]) -> bool {
+    let mut seen = HashSet::with_capacity(a.len());
+    !a.iter().all(|x| seen.insert(x))
+}
+
+/// Largest number of pairwise disjoint half-open intervals `[l, r)`, so touching ends do not overlap.
+/// Greedy by earliest end, sorting `intervals` by end as a side effect.
+pub fn max_non_overlapping(intervals: &mut [(i64, i64)]) -> usize {
+    intervals.sort_unstable_by_key(|&(l, r)| (r, l));
+    let mut end = i64::MIN;
+    let mut count = 0;
+    for &(l, r) in intervals.iter() {
+        if l >= end {
+            end = r;
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Number of distinct values in each half-open range `[l, r)` of `values`, answered offline in
+/// `O((n + q) log n)`. Sweeping right ends in order, only the last occurrence of each value so far is
+/// counted in a Fenwick tree, so a range counts the values whose last occurrence falls in it.
+pub fn distinct_in_ranges(values: &[u32], queries: &[(usize, usize)]) -> Vec<usize> {
+    let mut order = (0..queries.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&i| queries[i].1);
+    let mut last = HashMap::new();
+    let mut counted = Fenwick::<i64>::new(values.len());
+    let mut answers = vec![0; queries.len()];
+    let mut end = 0;
+    for i in order {
+        let (l, r) = queries[i];
+        assert!(
+            l <= r && r <= values.len(),
+            "query {}..{} out of range for length {}",
+            l,
+            r,
+            values.len()
+        );
+        while end < r {
+            if let Some(prev) = last.insert(values[end], end) {
+                counted.add(prev, -1);
+            }
+            counted.add(end, 1);
+            end += 1;
+        }
+        answers[i] = counted.sum(l..r) as usize;
+    }
+    answers
+}
+
+/// Index pairs `(i, j)` with `i < j < n` in lexicographic order.
+pub fn pairs(n: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..n).flat_map(move |i| (i + 1..n).map(move |j| (i, j)))
+}
+
+/// Index triples `(i, j, k)` with `i < j < k < n` in lexicographic order.
+pub fn triples(n: usize) -> impl Iterator<Item = (usize, usize, usize)> {
+    pairs(n).flat_map(move |(i, j)| (j + 1..n).map(move |k| (i, j, k)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    #[test]
+    fn majority_boundary() {
+        assert_eq!(majority::<i32>(&[]), None);
+        assert_eq!(majority(&[1]), Some(&1));
+        // exactly n/2 is not a majority
+        assert_eq!(majority(&[1, 2, 1, 2]), None);
+        assert_eq!(majority(&[1, 1, 2, 3]), None);
+        assert_eq!(majority(&[1, 2, 1, 2, 1]), Some(&1));
+        assert_eq!(majority(&[3, 1, 2, 3, 3]), Some(&3));
+    }
+
+    #[test]
+    fn frequent_elements_multiple() {
+        let a = [1, 2, 3, 1, 2, 1, 2, 4, 5];
+        // n / 3 = 3, so more than 3 occurrences are required
+        assert_eq!(frequent_elements(&a, 3), Vec::<&i32>::new());
+        // n / 4 = 2.25
+        assert_eq!(frequent_elements(&a, 4), vec![&1, &2]);
+        assert_eq!(frequent_elements(&a, 2), Vec::<&i32>::new());
+        assert_eq!(frequent_elements(&a, 100), vec![&1, &2, &3, &4, &5]);
+        // exactly n/k occurrences
+        assert_eq!(frequent_elements(&[1, 1, 2, 3], 2), Vec::<&i32>::new());
+    }
+
+    #[test]
+    fn planted_heavy_hitter() {
+        let mut rng = XorShift64::new(1);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(200);
+            let k = 2 + rng.gen_index(5);
+            let heavy = 1_000_000;
+            let planted = (n / k + 1).min(n);
+            let mut a = (0..n - planted)
+                .map(|_| rng.gen_range(0..50))
+                .collect::<Vec<_>>();
+            a.extend(vec![heavy; planted]);
+            rng.shuffle(&mut a);
+
+            let result = frequent_elements(&a, k);
+            assert!(result.contains(&&heavy));
+            let mut expected = HashMap::new();
+            for x in &a {
+                *expected.entry(x).or_insert(0) += 1;
+            }
+            let mut expected = expected
+                .into_iter()
+                .filter(|&(_, c)| c * k > a.len())
+                .map(|(x, _)| x)
+                .collect::<Vec<_>>();
+            let mut result = result;
+            expected.sort();
+            result.sort();
+            assert_eq!(result, expected);
+            if k == 2 {
+                assert_eq!(majority(&a), Some(&heavy));
+            }
+        }
+    }
+
+    #[test]
+    fn most_frequent() {
+        assert_eq!(mode(&[1, 2, 2, 3, 3, 3]), Some((3, 3)));
+        assert_eq!(mode(&["b", "a", "a", "b"]), Some(("b", 2)));
+        assert_eq!(mode::<i32>(&[]), None);
+        assert_eq!(counts(&[1, 2, 1])[&1], 2);
+    }
+
+    #[test]
+    fn find_duplicates() {
+        assert_eq!(duplicates(&[1, 2, 2, 3, 3, 3]), vec![2, 3]);
+        assert_eq!(duplicates(&[3, 1, 1, 3]), vec![1, 3]);
+        assert!(duplicates::<i32>(&[]).is_empty());
+        assert!(has_duplicates(&["a", "b", "a"]));
+        assert!(!has_duplicates(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn interval_scheduling() {
+        let mut intervals = vec![
+            (1, 4),
+            (3, 5),
+            (0, 6),
+            (5, 7),
+            (3, 9),
+            (5, 9),
+            (6, 10),
+            (8, 11),
+        ];
+        assert_eq!(max_non_overlapping(&mut intervals), 3);
+        assert_eq!(max_non_overlapping(&mut [(0, 1), (1, 2), (2, 3)]), 3);
+        assert_eq!(max_non_overlapping(&mut [(0, 10), (1, 2), (2, 3)]), 2);
+        assert_eq!(max_non_overlapping(&mut []), 0);
+    }
+
+    #[test]
+    fn distinct_counts_match_brute_force() {
+        assert_eq!(
+            distinct_in_ranges(&[1, 2, 1, 3, 2], &[(0, 5), (0, 3), (2, 4), (1, 1), (4, 5)]),
+            vec![3, 2, 2, 0, 1]
+        );
+        let mut rng = XorShift64::new(1518);
+        for _ in 0..50 {
+            let n = rng.gen_index(40);
+            let values = (0..n)
+                .map(|_| rng.gen_range(0..8) as u32)
+                .collect::<Vec<_>>();
+            let queries = (0..30)
+                .map(|_| {
+                    let l = rng.gen_index(n + 1);
+                    (l, l + rng.gen_index(n + 1 - l))
+                })
+                .collect::<Vec<_>>();
+            let expected = queries
+                .iter()
+                .map(|&(l, r)| values[l..r].iter().collect::<HashSet<_>>().len())
+                .collect::<Vec<_>>();
+            assert_eq!(distinct_in_ranges(&values, &queries), expected);
+        }
+    }
+
+    #[test]
+    fn enumerate_pairs() {
+        let all = pairs(4).collect::<Vec<_>>();
+        assert_eq!(all.len(), 6);
+        assert_eq!(all, vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+        assert_eq!(pairs(0).count(), 0);
+        assert_eq!(pairs(1).count(), 0);
+    }
+
+    #[test]
+    fn enumerate_triples() {
+        let all = triples(4).collect::<Vec<_>>();
+        assert_eq!(all, vec![(0, 1, 2), (0, 1, 3), (0, 2, 3), (1, 2, 3)]);
+        assert_eq!(triples(10).count(), 120);
+        assert_eq!(triples(2).count(), 0);
+    }
+}
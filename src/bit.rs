@@ -0,0 +1,31 @@
+//! Helpers for problems solved bit by bit.
+
+/// `result[i]` is the total number of set bits in `values[..i]`, so `result` has `values.len() + 1` entries.
+pub fn popcount_prefix(values: &[u64]) -> Vec<u32> {
+    let mut prefix = Vec::with_capacity(values.len() + 1);
+    prefix.push(0);
+    for v in values {
+        prefix.push(prefix.last().unwrap() + v.count_ones());
+    }
+    prefix
+}
+
+/// Number of values with `bit` set.
+pub fn count_bit_at(values: &[u64], bit: u32) -> usize {
+    assert!(bit < u64::BITS, "bit {} out of range", bit);
+    values.iter().filter(|&&v| v >> bit & 1 == 1).count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn small_array() {
+        let values = [0b101, 0b010, 0b111, 0b000, 0b001];
+        assert_eq!(popcount_prefix(&values), vec![0, 2, 3, 6, 6, 7]);
+        assert_eq!(count_bit_at(&values, 0), 3);
+        assert_eq!(count_bit_at(&values, 1), 2);
+        assert_eq!(count_bit_at(&values, 63), 0);
+    }
+}
@@ -0,0 +1,127 @@
+//! Vertex coloring of small graphs given as neighbor bitmasks (`adj[v] >> u & 1` iff `u` and `v` are adjacent).
+
+/// Modulus for the inclusion-exclusion sums, which are otherwise far too large.
+const MOD: u64 = 1_000_000_007;
+
+/// Minimum number of colors needed so that adjacent vertices differ, in `O(2^n n)`.
+///
+/// Counts the ways to cover the vertices by `k` independent sets with inclusion-exclusion,
+/// so `n` should be at most about 20.
+pub fn chromatic_number(adj: &[u64]) -> usize {
+    let n = adj.len();
+    if n == 0 {
+        return 0;
+    }
+    let full = (1usize << n) - 1;
+    // independent[s] is the number of independent subsets of s, including the empty one
+    let mut independent = vec![1; 1 << n];
+    for s in 1..=full {
+        let v = s.trailing_zeros() as usize;
+        let rest = s & !(1 << v);
+        independent[s] = (independent[rest] + independent[rest & !(adj[v] as usize)]) % MOD;
+    }
+    let mut power = vec![1; 1 << n];
+    for k in 1..n {
+        let mut total = 0;
+        for s in 0..=full {
+            power[s] = power[s] * independent[s] % MOD;
+            if (n - s.count_ones() as usize) % 2 == 0 {
+                total += power[s];
+            } else {
+                total += MOD - power[s];
+            }
+        }
+        if total % MOD != 0 {
+            return k;
+        }
+    }
+    n
+}
+
+/// A coloring with colors in `0..k` where adjacent vertices differ, found by backtracking.
+pub fn is_k_colorable_assignment(adj: &[u64], k: usize) -> Option<Vec<usize>> {
+    fn dfs(adj: &[u64], k: usize, colors: &mut Vec<usize>) -> bool {
+        let v = colors.len();
+        if v == adj.len() {
+            return true;
+        }
+        // only the next unused color is tried, since colors are interchangeable
+        let limit = k.min(colors.iter().max().map_or(1, |&c| c + 2));
+        for c in 0..limit {
+            if (0..v).any(|u| adj[v] >> u & 1 == 1 && colors[u] == c) {
+                continue;
+            }
+            colors.push(c);
+            if dfs(adj, k, colors) {
+                return true;
+            }
+            colors.pop();
+        }
+        false
+    }
+
+    let mut colors = Vec::with_capacity(adj.len());
+    dfs(adj, k, &mut colors).then_some(colors)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    fn masks(n: usize, edges: &[(usize, usize)]) -> Vec<u64> {
+        let mut adj = vec![0; n];
+        for &(u, v) in edges {
+            adj[u] |= 1 << v;
+            adj[v] |= 1 << u;
+        }
+        adj
+    }
+
+    fn cycle(n: usize) -> Vec<u64> {
+        masks(n, &(0..n).map(|v| (v, (v + 1) % n)).collect::<Vec<_>>())
+    }
+
+    fn assert_proper(adj: &[u64], colors: &[usize]) {
+        for (v, &mask) in adj.iter().enumerate() {
+            for (u, &c) in colors.iter().enumerate() {
+                assert!(mask >> u & 1 == 0 || colors[v] != c);
+            }
+        }
+    }
+
+    #[test]
+    fn known_graphs() {
+        for n in 1..=8 {
+            let complete = (0..n)
+                .map(|v| ((1 << n) - 1) & !(1 << v))
+                .collect::<Vec<_>>();
+            assert_eq!(chromatic_number(&complete), n);
+        }
+        assert_eq!(chromatic_number(&[]), 0);
+        assert_eq!(chromatic_number(&[0, 0, 0]), 1);
+        let bipartite = masks(7, &[(0, 4), (0, 5), (1, 5), (2, 6), (3, 4), (3, 6)]);
+        assert_eq!(chromatic_number(&bipartite), 2);
+        assert_eq!(chromatic_number(&cycle(6)), 2);
+        assert_eq!(chromatic_number(&cycle(5)), 3);
+        assert_eq!(chromatic_number(&cycle(9)), 3);
+    }
+
+    #[test]
+    fn assignment_matches_chromatic_number() {
+        let mut rng = XorShift64::new(27);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(10);
+            let edges = (0..rng.gen_index(n * n))
+                .map(|_| (rng.gen_index(n), rng.gen_index(n)))
+                .filter(|&(u, v)| u != v)
+                .collect::<Vec<_>>();
+            let adj = masks(n, &edges);
+            let k = chromatic_number(&adj);
+            let colors = is_k_colorable_assignment(&adj, k).unwrap();
+            assert!(colors.iter().all(|&c| c < k));
+            assert_proper(&adj, &colors);
+            assert_eq!(is_k_colorable_assignment(&adj, k - 1), None);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Polynomial multiplication by number theoretic transform.
+
+use crate::math::pow_mod;
+
+pub const MOD998244353: u64 = 998_244_353;
+
+/// NTT friendly primes `c * 2^k + 1` and a primitive root of each.
+const PRIMES: [(u64, u64); 3] = [(998_244_353, 3), (167_772_161, 3), (469_762_049, 3)];
+
+/// In place transform of `a`, whose length must be a power of two dividing `p - 1`.
+fn ntt(a: &mut [u64], p: u64, g: u64, invert: bool) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let mut w = pow_mod(g, (p - 1) / len as u64, p);
+        if invert {
+            w = pow_mod(w, p - 2, p);
+        }
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut wk = 1;
+            for (x, y) in lo.iter_mut().zip(hi.iter_mut()) {
+                let u = *x;
+                let v = *y * wk % p;
+                *x = if u + v >= p { u + v - p } else { u + v };
+                *y = if u >= v { u - v } else { u + p - v };
+                wk = wk * w % p;
+            }
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let inv_n = pow_mod(n as u64, p - 2, p);
+        a.iter_mut().for_each(|x| *x = *x * inv_n % p);
+    }
+}
+
+/// Product of `a` and `b` modulo the NTT friendly prime `p` with primitive root `g`.
+fn convolution_prime(a: &[u64], b: &[u64], p: u64, g: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+    assert_eq!(
+        (p - 1) % size as u64,
+        0,
+        "length {} is too long for {}",
+        len,
+        p
+    );
+    let mut fa = a.iter().map(|&x| x % p).collect::<Vec<_>>();
+    let mut fb = b.iter().map(|&x| x % p).collect::<Vec<_>>();
+    fa.resize(size, 0);
+    fb.resize(size, 0);
+    ntt(&mut fa, p, g, false);
+    ntt(&mut fb, p, g, false);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x = *x * y % p;
+    }
+    ntt(&mut fa, p, g, true);
+    fa.truncate(len);
+    fa
+}
+
+/// Product of `a` and `b` modulo 998244353.
+pub fn convolution(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (p, g) = PRIMES[0];
+    convolution_prime(a, b, p, g)
+}
+
+/// Product of `a` and `b` modulo an arbitrary `m < 2^30`, such as 10^9+7.
+///
+/// Computes the exact coefficients modulo three NTT primes and reconstructs them with CRT.
+/// Their product exceeds `2^80`, which bounds every coefficient for lengths up to `2^20`.
+pub fn convolution_mod(a: &[u64], b: &[u64], m: u64) -> Vec<u64> {
+    assert!(0 < m && m < 1 << 30, "modulus {} is out of range", m);
+    let a = a.iter().map(|&x| x % m).collect::<Vec<_>>();
+    let b = b.iter().map(|&x| x % m).collect::<Vec<_>>();
+    let [(p1, g1), (p2, g2), (p3, g3)] = PRIMES;
+    let c1 = convolution_prime(&a, &b, p1, g1);
+    let c2 = convolution_prime(&a, &b, p2, g2);
+    let c3 = convolution_prime(&a, &b, p3, g3);
+
+    let inv_p1_mod_p2 = pow_mod(p1, p2 - 2, p2);
+    let p1p2 = p1 as u128 * p2 as u128;
+    let inv_p1p2_mod_p3 = pow_mod((p1p2 % p3 as u128) as u64, p3 - 2, p3);
+    c1.into_iter()
+        .zip(c2)
+        .zip(c3)
+        .map(|((r1, r2), r3)| {
+            // Garner: x = r1 + p1 * t1 + p1 * p2 * t2
+            let t1 = (r2 + p2 - r1 % p2) % p2 * inv_p1_mod_p2 % p2;
+            let x12 = r1 as u128 + p1 as u128 * t1 as u128;
+            let t2 = (r3 + p3 - (x12 % p3 as u128) as u64) % p3 * inv_p1p2_mod_p3 % p3;
+            let x = x12 + p1p2 * t2 as u128;
+            (x % m as u128) as u64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    fn naive(a: &[u64], b: &[u64], m: u64) -> Vec<u64> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+        let mut c = vec![0; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] = (c[i + j] + x * y) % m;
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn convolution_998244353() {
+        assert_eq!(convolution(&[1, 2, 3], &[4, 5]), vec![4, 13, 22, 15]);
+        assert_eq!(convolution(&[], &[1]), Vec::<u64>::new());
+        let mut rng = XorShift64::new(998);
+        let a = (0..100)
+            .map(|_| rng.gen_range(0..MOD998244353))
+            .collect::<Vec<_>>();
+        let b = (0..77)
+            .map(|_| rng.gen_range(0..MOD998244353))
+            .collect::<Vec<_>>();
+        assert_eq!(convolution(&a, &b), naive(&a, &b, MOD998244353));
+    }
+
+    #[test]
+    fn arbitrary_modulus_matches_naive() {
+        let m = 1_000_000_007;
+        let mut rng = XorShift64::new(1_000_000_007);
+        for _ in 0..20 {
+            let a = (0..1 + rng.gen_index(300))
+                .map(|_| rng.gen_range(0..m))
+                .collect::<Vec<_>>();
+            let b = (0..1 + rng.gen_index(300))
+                .map(|_| rng.gen_range(0..m))
+                .collect::<Vec<_>>();
+            assert_eq!(convolution_mod(&a, &b, m), naive(&a, &b, m));
+        }
+    }
+
+    #[test]
+    fn maximal_values_do_not_overflow() {
+        // each coefficient sums up to n terms of (m - 1)^2, the largest possible products
+        let m = (1 << 30) - 1;
+        let n = 1 << 14;
+        let a = vec![m - 1; n];
+        let c = convolution_mod(&a, &a, m);
+        assert_eq!(c.len(), 2 * n - 1);
+        for (k, &x) in c.iter().enumerate() {
+            let terms = (k.min(2 * n - 2 - k) + 1) as u128;
+            let expected = terms * (m as u128 - 1) * (m as u128 - 1) % m as u128;
+            assert_eq!(x as u128, expected);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Proleptic Gregorian calendar arithmetic.
+//!
+//! Days are counted from 1970-01-01 following Howard Hinnant's `days_from_civil` algorithms.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Days since Monday, `0..7`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+pub fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap(year) => 29,
+        2 => 28,
+        _ => panic!("invalid month {}", month),
+    }
+}
+
+/// Days from 1970-01-01 to the given date, negative before it.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    debug_assert!((1..=12).contains(&month) && 1 <= day && day <= days_in_month(year, month));
+    // years start in March so the leap day is the last day of the year
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of [`days_from_civil`], returning `(year, month, day)`.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+pub fn day_of_week(year: i64, month: u32, day: u32) -> Weekday {
+    // 1970-01-01 was a Thursday
+    let days = days_from_civil(year, month, day);
+    Weekday::ALL[(days + Weekday::Thursday.index() as i64).rem_euclid(7) as usize]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    #[test]
+    fn leap_years() {
+        assert!(!is_leap(1900));
+        assert!(is_leap(2000));
+        assert!(is_leap(2024));
+        assert!(!is_leap(2023));
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(2023, 12), 31);
+    }
+
+    #[test]
+    fn anchors() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(
+            days_from_civil(2000, 3, 1) - days_from_civil(2000, 2, 28),
+            2
+        );
+        assert_eq!(
+            days_from_civil(1900, 3, 1) - days_from_civil(1900, 2, 28),
+            1
+        );
+        assert_eq!(day_of_week(2000, 1, 1), Weekday::Saturday);
+        assert_eq!(day_of_week(1970, 1, 1), Weekday::Thursday);
+        assert_eq!(day_of_week(1969, 12, 31), Weekday::Wednesday);
+        assert_eq!(day_of_week(2023, 7, 17), Weekday::Monday);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = XorShift64::new(11);
+        for _ in 0..10_000 {
+            let year = rng.gen_range(0..6000) as i64 - 3000;
+            let month = rng.gen_range(1..13) as u32;
+            let day = rng.gen_range(1..days_in_month(year, month) as u64 + 1) as u32;
+            let days = days_from_civil(year, month, day);
+            assert_eq!(civil_from_days(days), (year, month, day));
+            assert_eq!(civil_from_days(days + 1), next_day(year, month, day));
+        }
+    }
+
+    fn next_day(year: i64, month: u32, day: u32) -> (i64, u32, u32) {
+        if day < days_in_month(year, month) {
+            (year, month, day + 1)
+        } else if month < 12 {
+            (year, month + 1, 1)
+        } else {
+            (year + 1, 1, 1)
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Dominator tree of a directed graph.
+
+const NONE: usize = usize::MAX;
+
+/// Immediate dominator of every vertex reachable from `source` by the Lengauer-Tarjan algorithm,
+/// in `O(m log n)`. The source and unreachable vertices have `None`.
+pub fn dominator_tree(graph: &[Vec<usize>], source: usize) -> Vec<Option<usize>> {
+    let n = graph.len();
+    // preorder numbering; everything below works on these numbers
+    let mut dfn = vec![NONE; n];
+    let mut order = Vec::with_capacity(n);
+    let mut parent = Vec::with_capacity(n);
+    let mut stack = vec![(source, NONE)];
+    while let Some((v, p)) = stack.pop() {
+        if dfn[v] != NONE {
+            continue;
+        }
+        dfn[v] = order.len();
+        order.push(v);
+        parent.push(p);
+        for &u in graph[v].iter().rev() {
+            if dfn[u] == NONE {
+                stack.push((u, dfn[v]));
+            }
+        }
+    }
+    let k = order.len();
+    let mut preds = vec![Vec::new(); k];
+    for (v, edges) in graph.iter().enumerate() {
+        if dfn[v] == NONE {
+            continue;
+        }
+        for &u in edges {
+            preds[dfn[u]].push(dfn[v]);
+        }
+    }
+
+    let mut semi = (0..k).collect::<Vec<_>>();
+    let mut idom = vec![0; k];
+    let mut ancestor = vec![NONE; k];
+    let mut label = (0..k).collect::<Vec<_>>();
+    let mut bucket = vec![Vec::new(); k];
+    for w in (1..k).rev() {
+        for &v in &preds[w] {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            semi[w] = semi[w].min(semi[u]);
+        }
+        bucket[semi[w]].push(w);
+        let p = parent[w];
+        ancestor[w] = p;
+        for v in std::mem::take(&mut bucket[p]) {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            idom[v] = if semi[u] < semi[v] { u } else { p };
+        }
+    }
+    for w in 1..k {
+        if idom[w] != semi[w] {
+            idom[w] = idom[idom[w]];
+        }
+    }
+
+    let mut result = vec![None; n];
+    for w in 1..k {
+        result[order[w]] = Some(order[idom[w]]);
+    }
+    result
+}
+
+/// Vertex with the minimum semidominator on the path from `v` up to its forest root, excluding the root.
+fn eval(v: usize, ancestor: &mut [usize], label: &mut [usize], semi: &[usize]) -> usize {
+    if ancestor[v] == NONE {
+        return v;
+    }
+    // path compression, from the top of the path down to v
+    let mut path = Vec::new();
+    let mut x = v;
+    while ancestor[ancestor[x]] != NONE {
+        path.push(x);
+        x = ancestor[x];
+    }
+    for &x in path.iter().rev() {
+        let a = ancestor[x];
+        if semi[label[a]] < semi[label[x]] {
+            label[x] = label[a];
+        }
+        ancestor[x] = ancestor[a];
+    }
+    label[v]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut g = vec![Vec::new(); n];
+        for &(u, v) in edges {
+            g[u].push(v);
+        }
+        g
+    }
+
+    fn reachable(g: &[Vec<usize>], source: usize, removed: usize) -> Vec<bool> {
+        let mut seen = vec![false; g.len()];
+        if source == removed {
+            return seen;
+        }
+        seen[source] = true;
+        let mut stack = vec![source];
+        while let Some(v) = stack.pop() {
+            for &u in &g[v] {
+                if u != removed && !seen[u] {
+                    seen[u] = true;
+                    stack.push(u);
+                }
+            }
+        }
+        seen
+    }
+
+    /// `d` dominates `v` iff removing `d` disconnects `v`; the immediate one is dominated by all others.
+    fn brute_force(g: &[Vec<usize>], source: usize) -> Vec<Option<usize>> {
+        let n = g.len();
+        let all = reachable(g, source, usize::MAX);
+        let mut dominators = vec![Vec::new(); n];
+        for d in 0..n {
+            let without = reachable(g, source, d);
+            for v in 0..n {
+                if v != d && all[v] && !without[v] {
+                    dominators[v].push(d);
+                }
+            }
+        }
+        (0..n)
+            .map(|v| {
+                dominators[v]
+                    .iter()
+                    .copied()
+                    .max_by_key(|&d| dominators[d].len())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn diamond() {
+        let g = graph(6, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 4)]);
+        let idom = dominator_tree(&g, 0);
+        assert_eq!(idom, vec![None, Some(0), Some(0), Some(0), Some(3), None]);
+    }
+
+    #[test]
+    fn unreachable_and_cycles() {
+        let g = graph(5, &[(1, 2), (2, 1), (1, 0), (3, 4)]);
+        assert_eq!(
+            dominator_tree(&g, 1),
+            vec![Some(1), None, Some(1), None, None]
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = XorShift64::new(25);
+        for _ in 0..200 {
+            let n = 1 + rng.gen_index(12);
+            let m = rng.gen_index(3 * n);
+            let edges = (0..m)
+                .map(|_| (rng.gen_index(n), rng.gen_index(n)))
+                .collect::<Vec<_>>();
+            let g = graph(n, &edges);
+            let source = rng.gen_index(n);
+            assert_eq!(dominator_tree(&g, source), brute_force(&g, source));
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Dual segment tree: range update and point query for a commutative operation such as `max`.
+
+use std::ops::RangeBounds;
+
+use crate::range::bounds;
+
+pub struct DualSegmentTree<T, F> {
+    n: usize,
+    size: usize,
+    /// Pending updates of each node, applied to every leaf below it.
+    lazy: Vec<T>,
+    op: F,
+}
+
+impl<T, F> DualSegmentTree<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    /// `op` must be commutative and associative, since updates are combined in no particular order.
+    pub fn new(n: usize, identity: T, op: F) -> Self {
+        let size = n.next_power_of_two();
+        Self {
+            n,
+            size,
+            lazy: vec![identity; 2 * size],
+            op,
+        }
+    }
+
+    /// Build with `values` as the leaves.
+    pub fn from_vec(values: Vec<T>, identity: T, op: F) -> Self {
+        let mut tree = Self::new(values.len(), identity, op);
+        for (i, v) in values.into_iter().enumerate() {
+            tree.lazy[tree.size + i] = v;
+        }
+        tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// `x = op(x, value)` for every `x` in `range`, e.g. chmax when `op` is `max`.
+    pub fn apply_range(&mut self, range: impl RangeBounds<usize>, value: T) {
+        let (l, r) = bounds(range, self.n);
+        let (mut l, mut r) = (l + self.size, r + self.size);
+        while l < r {
+            if l & 1 == 1 {
+                self.lazy[l] = (self.op)(&self.lazy[l], &value);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                self.lazy[r] = (self.op)(&self.lazy[r], &value);
+            }
+            l /= 2;
+            r /= 2;
+        }
+    }
+
+    /// Value at `i` after every update so far.
+    pub fn get(&self, i: usize) -> T {
+        assert!(i < self.n, "index {} out of range for length {}", i, self.n);
+        let mut i = i + self.size;
+        let mut value = self.lazy[i].clone();
+        while i > 1 {
+            i /= 2;
+            value = (self.op)(&value, &self.lazy[i]);
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    #[test]
+    fn overlapping_chmax() {
+        let mut tree =
+            DualSegmentTree::from_vec(vec![0, 5, 0, 0, 0, 0], i64::MIN, |a, b| *a.max(b));
+        tree.apply_range(0..4, 3);
+        tree.apply_range(2..=5, 7);
+        tree.apply_range(3..4, 1);
+        tree.apply_range(.., 2);
+        let values = (0..tree.len()).map(|i| tree.get(i)).collect::<Vec<_>>();
+        assert_eq!(values, vec![3, 5, 7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn random_chmax() {
+        let mut rng = XorShift64::new(36);
+        let n = 50;
+        let mut a = vec![0; n];
+        let mut tree = DualSegmentTree::new(n, 0, |x: &u64, y: &u64| *x.max(y));
+        for _ in 0..500 {
+            let l = rng.gen_index(n + 1);
+            let r = l + rng.gen_index(n + 1 - l);
+            let v = rng.gen_range(0..1000);
+            a[l..r].iter_mut().for_each(|x| *x = (*x).max(v));
+            tree.apply_range(l..r, v);
+            let i = rng.gen_index(n);
+            assert_eq!(tree.get(i), a[i]);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Segment tree over a huge index space, allocating nodes only on the paths of updated indices.
+
+use std::ops::RangeBounds;
+
+use crate::range::bounds;
+
+/// Child index `0` means absent, as the root never is a child.
+const NONE: usize = 0;
+
+struct Node<T> {
+    value: T,
+    children: [usize; 2],
+}
+
+pub struct DynamicSegmentTree<T, F> {
+    n: u64,
+    nodes: Vec<Node<T>>,
+    identity: T,
+    op: F,
+}
+
+impl<T, F> DynamicSegmentTree<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    /// Every value in `0..n` starts as `identity`. Each update allocates `O(log n)` nodes.
+    pub fn new(n: u64, identity: T, op: F) -> Self {
+        Self {
+            n,
+            nodes: vec![Node {
+                value: identity.clone(),
+                children: [NONE; 2],
+            }],
+            identity,
+            op,
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn update(&mut self, i: u64, value: T) {
+        assert!(i < self.n, "index {} out of range for length {}", i, self.n);
+        // descend to the leaf, then recompute the nodes on the path bottom up
+        let mut path = vec![0];
+        let (mut lo, mut hi) = (0, self.n);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            let side = usize::from(i >= mid);
+            if side == 0 {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+            let v = *path.last().unwrap();
+            if self.nodes[v].children[side] == NONE {
+                self.nodes[v].children[side] = self.nodes.len();
+                self.nodes.push(Node {
+                    value: self.identity.clone(),
+                    children: [NONE; 2],
+                });
+            }
+            path.push(self.nodes[v].children[side]);
+        }
+        self.nodes[path.pop().unwrap()].value = value;
+        while let Some(v) = path.pop() {
+            let [l, r] = self.nodes[v].children;
+            self.nodes[v].value = (self.op)(&self.value(l), &self.value(r));
+        }
+    }
+
+    fn value(&self, v: usize) -> T {
+        if v == NONE {
+            self.identity.clone()
+        } else {
+            self.nodes[v].value.clone()
+        }
+    }
+
+    pub fn get(&self, i: u64) -> T {
+        self.query(i..=i)
+    }
+
+    /// Fold of the values in `range`.
+    pub fn query(&self, range: impl RangeBounds<u64>) -> T {
+        let (l, r) = bounds(range, self.n);
+        self.fold(0, 0, self.n, l, r)
+    }
+
+    /// Fold of `[l, r)` within node `v` covering `[lo, hi)`.
+    fn fold(&self, v: usize, lo: u64, hi: u64, l: u64, r: u64) -> T {
+        if r <= lo || hi <= l {
+            return self.identity.clone();
+        }
+        if l <= lo && hi <= r {
+            return self.nodes[v].value.clone();
+        }
+        let mid = lo + (hi - lo) / 2;
+        let [left, right] = self.nodes[v].children;
+        let left = if left == NONE {
+            self.identity.clone()
+        } else {
+            self.fold(left, lo, mid, l, r)
+        };
+        let right = if right == NONE {
+            self.identity.clone()
+        } else {
+            self.fold(right, mid, hi, l, r)
+        };
+        (self.op)(&left, &right)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn huge_indices() {
+        let n = 1_000_000_000_000_000_001;
+        let mut tree = DynamicSegmentTree::new(n, 0i64, |a, b| a + b);
+        tree.update(0, 1);
+        tree.update(1_000_000_000_000_000_000, 5);
+        tree.update(123_456_789_012_345, 10);
+        tree.update(123_456_789_012_345, 7);
+        assert_eq!(tree.query(..), 13);
+        assert_eq!(tree.query(1..), 12);
+        assert_eq!(tree.query(..123_456_789_012_345), 1);
+        assert_eq!(tree.query(123_456_789_012_345..n - 1), 7);
+        assert_eq!(tree.get(1_000_000_000_000_000_000), 5);
+        assert_eq!(tree.get(42), 0);
+        assert!(tree.nodes.len() <= 3 * 61);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = XorShift64::new(48);
+        let n = 1 << 40;
+        let mut tree = DynamicSegmentTree::new(n, u64::MAX, |a: &u64, b: &u64| *a.min(b));
+        let mut values = BTreeMap::new();
+        for _ in 0..300 {
+            let i = rng.gen_range(0..n);
+            let v = rng.gen_range(0..1000);
+            tree.update(i, v);
+            values.insert(i, v);
+            let a = rng.gen_range(0..n + 1);
+            let b = rng.gen_range(0..n + 1);
+            let (l, r) = (a.min(b), a.max(b));
+            let expected = values
+                .range(l..r)
+                .map(|(_, &v)| v)
+                .min()
+                .unwrap_or(u64::MAX);
+            assert_eq!(tree.query(l..r), expected);
+        }
+    }
+}
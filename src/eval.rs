@@ -0,0 +1,96 @@
+//! Integer arithmetic expressions, e.g. the tokens of a line read by `Scanner::scan_line_tokens`.
+
+/// Evaluate infix tokens with `+ - * /` and parentheses by the shunting-yard algorithm.
+///
+/// `*` and `/` bind tighter than `+` and `-`, all are left associative, and `/` truncates toward zero.
+/// Panics on malformed expressions.
+pub fn evaluate(tokens: &[String]) -> i64 {
+    let mut values = Vec::new();
+    let mut ops: Vec<&str> = Vec::new();
+    for token in tokens {
+        match token.as_str() {
+            "(" => ops.push("("),
+            ")" => loop {
+                match ops.pop() {
+                    Some("(") => break,
+                    Some(op) => apply(&mut values, op),
+                    None => panic!("unbalanced parentheses in {:?}", tokens),
+                }
+            },
+            op @ ("+" | "-" | "*" | "/") => {
+                while let Some(&top) = ops.last() {
+                    if top == "(" || precedence(top) < precedence(op) {
+                        break;
+                    }
+                    apply(&mut values, top);
+                    ops.pop();
+                }
+                ops.push(op);
+            }
+            number => values.push(
+                number
+                    .parse()
+                    .unwrap_or_else(|err| panic!("invalid token {:?}: {}", number, err)),
+            ),
+        }
+    }
+    while let Some(op) = ops.pop() {
+        assert_ne!(op, "(", "unbalanced parentheses in {:?}", tokens);
+        apply(&mut values, op);
+    }
+    assert_eq!(values.len(), 1, "malformed expression {:?}", tokens);
+    values[0]
+}
+
+fn precedence(op: &str) -> u8 {
+    match op {
+        "+" | "-" => 1,
+        "*" | "/" => 2,
+        _ => unreachable!(),
+    }
+}
+
+fn apply(values: &mut Vec<i64>, op: &str) {
+    let (Some(rhs), Some(lhs)) = (values.pop(), values.pop()) else {
+        panic!("missing operand for {}", op);
+    };
+    values.push(match op {
+        "+" => lhs + rhs,
+        "-" => lhs - rhs,
+        "*" => lhs * rhs,
+        "/" => lhs / rhs,
+        _ => unreachable!(),
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn eval_str(s: &str) -> i64 {
+        let tokens = s.split(' ').map(String::from).collect::<Vec<_>>();
+        evaluate(&tokens)
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(eval_str("1 + 2 * 3"), 7);
+        assert_eq!(eval_str("10 - 4 - 3"), 3);
+        assert_eq!(eval_str("100 / 10 / 5"), 2);
+        assert_eq!(eval_str("( 1 + 2 ) * 3"), 9);
+        assert_eq!(eval_str("7 / 2 - 8 / 3 * 2"), -1);
+        assert_eq!(eval_str("42"), 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "unbalanced parentheses")]
+    fn extra_close_paren() {
+        eval_str("1 + 2 )");
+    }
+
+    #[test]
+    #[should_panic(expected = "unbalanced parentheses")]
+    fn unclosed_open_paren() {
+        eval_str("( 1 + 2");
+    }
+}
@@ -0,0 +1,164 @@
+//! Binary indexed trees.
+
+use std::ops::{Add, Mul, RangeBounds, Sub};
+
+use crate::range::bounds;
+
+/// Point add, range sum.
+#[derive(Debug, Clone)]
+pub struct Fenwick<T> {
+    data: Vec<T>,
+}
+
+impl<T> Fenwick<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new(n: usize) -> Self {
+        Self {
+            data: vec![T::default(); n + 1],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn add(&mut self, i: usize, delta: T) {
+        assert!(
+            i < self.len(),
+            "index {} out of range for length {}",
+            i,
+            self.len()
+        );
+        let mut i = i + 1;
+        while i < self.data.len() {
+            self.data[i] = self.data[i] + delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Sum of `[0, r)`.
+    pub fn prefix_sum(&self, r: usize) -> T {
+        let mut r = r;
+        let mut sum = T::default();
+        while r > 0 {
+            sum = sum + self.data[r];
+            r -= r & r.wrapping_neg();
+        }
+        sum
+    }
+
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = bounds(range, self.len());
+        self.prefix_sum(r) - self.prefix_sum(l)
+    }
+}
+
+/// Range add, range sum, using two [`Fenwick`]s.
+///
+/// After adding `x` to `[l, r)`, the prefix sum up to `i` grows by `x * (i - l)` for `l <= i < r`
+/// and by `x * (r - l)` for `r <= i`, which is `b(i) * i + c(i)` for piecewise constant `b` and `c`.
+#[derive(Debug, Clone)]
+pub struct FenwickRange<T> {
+    slope: Fenwick<T>,
+    offset: Fenwick<T>,
+}
+
+impl<T> FenwickRange<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + TryFrom<usize>,
+{
+    pub fn new(n: usize) -> Self {
+        Self {
+            slope: Fenwick::new(n + 1),
+            offset: Fenwick::new(n + 1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slope.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn add(&mut self, range: impl RangeBounds<usize>, delta: T) {
+        let (l, r) = bounds(range, self.len());
+        self.slope.add(l, delta);
+        self.slope.add(r, T::default() - delta);
+        self.offset.add(l, T::default() - delta * Self::index(l));
+        self.offset.add(r, delta * Self::index(r));
+    }
+
+    /// Sum of `[0, r)`.
+    pub fn prefix_sum(&self, r: usize) -> T {
+        self.slope.prefix_sum(r) * Self::index(r) + self.offset.prefix_sum(r)
+    }
+
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = bounds(range, self.len());
+        self.prefix_sum(r) - self.prefix_sum(l)
+    }
+
+    fn index(i: usize) -> T {
+        T::try_from(i)
+            .ok()
+            .expect("index is not representable in the value type")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    #[test]
+    fn point_add_range_sum() {
+        let mut fenwick = Fenwick::new(5);
+        for (i, v) in [3, 1, 4, 1, 5].into_iter().enumerate() {
+            fenwick.add(i, v);
+        }
+        assert_eq!(fenwick.sum(..), 14);
+        assert_eq!(fenwick.sum(1..4), 6);
+        fenwick.add(2, -4);
+        assert_eq!(fenwick.sum(2..=2), 0);
+    }
+
+    #[test]
+    fn range_add_range_sum() {
+        let mut fenwick = FenwickRange::<i64>::new(8);
+        fenwick.add(1..5, 3);
+        fenwick.add(3..8, -2);
+        // [0, 3, 3, 1, 1, -2, -2, -2]
+        assert_eq!(fenwick.sum(..), 2);
+        assert_eq!(fenwick.sum(0..4), 7);
+        assert_eq!(fenwick.sum(2..6), 3);
+        assert_eq!(fenwick.sum(4..=4), 1);
+        assert_eq!(fenwick.sum(5..5), 0);
+    }
+
+    #[test]
+    fn range_add_random() {
+        let mut rng = XorShift64::new(8);
+        let n = 50;
+        let mut naive = vec![0i64; n];
+        let mut fenwick = FenwickRange::new(n);
+        for _ in 0..1000 {
+            let l = rng.gen_index(n + 1);
+            let r = l + rng.gen_index(n + 1 - l);
+            if rng.next_u64() % 2 == 0 {
+                let x = rng.gen_range(0..200) as i64 - 100;
+                naive[l..r].iter_mut().for_each(|v| *v += x);
+                fenwick.add(l..r, x);
+            } else {
+                assert_eq!(fenwick.sum(l..r), naive[l..r].iter().sum::<i64>());
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Floating point plane geometry.
+
+use crate::random::XorShift64;
+
+pub type PointF = (f64, f64);
+
+const EPS: f64 = 1e-9;
+
+fn dist(a: PointF, b: PointF) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// Smallest circle containing every point, as `(center, radius)`, by Welzl's algorithm in expected `O(n)`.
+///
+/// The points are shuffled with a fixed seed, so the result is deterministic.
+pub fn min_enclosing_circle(points: &[PointF]) -> (PointF, f64) {
+    assert!(!points.is_empty(), "no points to enclose");
+    let mut p = points.to_vec();
+    XorShift64::new(31).shuffle(&mut p);
+    let inside = |(c, r): (PointF, f64), q: PointF| dist(c, q) <= r + EPS * r.max(1.0);
+
+    let mut circle = (p[0], 0.0);
+    for i in 1..p.len() {
+        if inside(circle, p[i]) {
+            continue;
+        }
+        // p[i] is on the boundary of the circle of p[..=i]
+        circle = (p[i], 0.0);
+        for j in 0..i {
+            if inside(circle, p[j]) {
+                continue;
+            }
+            circle = diameter_circle(p[i], p[j]);
+            for k in 0..j {
+                if !inside(circle, p[k]) {
+                    circle = circumcircle(p[i], p[j], p[k]);
+                }
+            }
+        }
+        // move to front: points that forced a new circle are likely to do it again
+        p[..=i].rotate_right(1);
+    }
+    circle
+}
+
+fn diameter_circle(a: PointF, b: PointF) -> (PointF, f64) {
+    (((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0), dist(a, b) / 2.0)
+}
+
+/// Circle through `a`, `b` and `c`, or the circle over the farthest pair if they are collinear.
+fn circumcircle(a: PointF, b: PointF, c: PointF) -> (PointF, f64) {
+    let (bx, by) = (b.0 - a.0, b.1 - a.1);
+    let (cx, cy) = (c.0 - a.0, c.1 - a.1);
+    let d = 2.0 * (bx * cy - by * cx);
+    if d.abs() < EPS {
+        return [
+            diameter_circle(a, b),
+            diameter_circle(b, c),
+            diameter_circle(c, a),
+        ]
+        .into_iter()
+        .max_by(|x, y| x.1.total_cmp(&y.1))
+        .unwrap();
+    }
+    let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+    let ux = (cy * b2 - by * c2) / d;
+    let uy = (bx * c2 - cx * b2) / d;
+    ((a.0 + ux, a.1 + uy), ux.hypot(uy))
+}
+
+/// Cross product of `b - a` and `c - a`, positive if `c` is to the left of the directed line `ab`.
+fn cross(a: PointF, b: PointF, c: PointF) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Signed area of a polygon, positive when its vertices are counterclockwise.
+pub fn polygon_area(poly: &[PointF]) -> f64 {
+    let n = poly.len();
+    (0..n)
+        .map(|i| {
+            let (p, q) = (poly[i], poly[(i + 1) % n]);
+            p.0 * q.1 - p.1 * q.0
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Part of the convex polygon `poly` to the left of the directed line `ab`, vertices kept in order.
+pub fn clip_polygon(poly: &[PointF], a: PointF, b: PointF) -> Vec<PointF> {
+    let n = poly.len();
+    let mut clipped = Vec::with_capacity(n + 1);
+    for i in 0..n {
+        let (p, q) = (poly[i], poly[(i + 1) % n]);
+        let (cp, cq) = (cross(a, b, p), cross(a, b, q));
+        if cp >= -EPS {
+            clipped.push(p);
+        }
+        if (cp > EPS && cq < -EPS) || (cp < -EPS && cq > EPS) {
+            // measured along ab rather than pq, which may be a long edge of the bounding square
+            let (ca, cb) = (cross(p, q, a), cross(p, q, b));
+            let t = ca / (ca - cb);
+            clipped.push((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t));
+        }
+    }
+    clipped
+}
+
+/// Half-planes are clipped out of a square of this half-width, so coordinates must stay well inside it.
+const BOUND: f64 = 1e9;
+
+/// Intersection of the half-planes to the left of each directed line `(a, b)`, as a counterclockwise
+/// polygon, or `None` if it has no area.
+///
+/// The intersection must be bounded. Clips a bounding square by each plane in turn, `O(n^2)`.
+pub fn half_plane_intersection(planes: &[(PointF, PointF)]) -> Option<Vec<PointF>> {
+    let mut poly = vec![
+        (-BOUND, -BOUND),
+        (BOUND, -BOUND),
+        (BOUND, BOUND),
+        (-BOUND, BOUND),
+    ];
+    for &(a, b) in planes {
+        poly = clip_polygon(&poly, a, b);
+        if poly.len() < 3 {
+            return None;
+        }
+    }
+    (polygon_area(&poly) > EPS).then_some(poly)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-7,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn assert_circle((c, r): (PointF, f64), center: PointF, radius: f64) {
+        assert_close(c.0, center.0);
+        assert_close(c.1, center.1);
+        assert_close(r, radius);
+    }
+
+    #[test]
+    fn small_cases() {
+        assert_circle(min_enclosing_circle(&[(1.0, 2.0)]), (1.0, 2.0), 0.0);
+        assert_circle(
+            min_enclosing_circle(&[(0.0, 0.0), (4.0, 0.0)]),
+            (2.0, 0.0),
+            2.0,
+        );
+        // obtuse triangle: the longest side is a diameter
+        assert_circle(
+            min_enclosing_circle(&[(0.0, 0.0), (4.0, 0.0), (1.0, 1.0)]),
+            (2.0, 0.0),
+            2.0,
+        );
+        // acute triangle: the circumcircle
+        assert_circle(
+            min_enclosing_circle(&[(0.0, 0.0), (2.0, 0.0), (1.0, 3.0)]),
+            (1.0, 4.0 / 3.0),
+            5.0 / 3.0,
+        );
+        let collinear = (0..10).map(|i| (i as f64, i as f64)).collect::<Vec<_>>();
+        assert_circle(
+            min_enclosing_circle(&collinear),
+            (4.5, 4.5),
+            4.5 * 2f64.sqrt(),
+        );
+    }
+
+    #[test]
+    fn clip_square_by_diagonal() {
+        let square = [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)];
+        // the kept side is left of the directed line, above the diagonal when it goes up
+        let above = clip_polygon(&square, (0.0, 0.0), (2.0, 2.0));
+        assert_eq!(above, vec![(0.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+        assert_close(polygon_area(&above), 2.0);
+        let below = clip_polygon(&square, (2.0, 2.0), (0.0, 0.0));
+        assert_eq!(below, vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0)]);
+        // a line crossing two edges
+        let corner = clip_polygon(&square, (1.0, 0.0), (0.0, 1.0));
+        assert_close(polygon_area(&corner), 0.5);
+        assert_eq!(clip_polygon(&square, (0.0, 3.0), (1.0, 3.0)), vec![]);
+    }
+
+    #[test]
+    fn half_planes() {
+        // 0 <= x <= 3, 0 <= y <= 2, x + y <= 4
+        let planes = [
+            ((0.0, 0.0), (1.0, 0.0)),
+            ((3.0, 0.0), (3.0, 1.0)),
+            ((0.0, 2.0), (-1.0, 2.0)),
+            ((0.0, 0.0), (0.0, -1.0)),
+            ((4.0, 0.0), (0.0, 4.0)),
+        ];
+        let poly = half_plane_intersection(&planes).unwrap();
+        assert_close(polygon_area(&poly), 6.0 - 0.5);
+        assert_eq!(poly.len(), 5);
+
+        // x >= 2 and x <= 1
+        let empty = [
+            ((2.0, 0.0), (2.0, -1.0)),
+            ((1.0, 0.0), (1.0, 1.0)),
+            ((0.0, 0.0), (1.0, 0.0)),
+            ((0.0, 5.0), (-1.0, 5.0)),
+        ];
+        assert_eq!(half_plane_intersection(&empty), None);
+
+        // triangle 0 <= y, x <= 1, y <= x touching at a single point with y >= 1
+        let point = [
+            ((0.0, 0.0), (1.0, 0.0)),
+            ((1.0, 0.0), (1.0, 1.0)),
+            ((1.0, 1.0), (0.0, 0.0)),
+            ((0.0, 1.0), (1.0, 1.0)),
+        ];
+        assert_close(
+            polygon_area(&half_plane_intersection(&point[..3]).unwrap()),
+            0.5,
+        );
+        assert_eq!(half_plane_intersection(&point), None);
+    }
+
+    #[test]
+    fn random_points() {
+        let mut rng = XorShift64::new(3131);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_index(30);
+            let points = (0..n)
+                .map(|_| (rng.gen_f64() * 200.0 - 100.0, rng.gen_f64() * 200.0 - 100.0))
+                .collect::<Vec<_>>();
+            let (c, r) = min_enclosing_circle(&points);
+            assert!(points.iter().all(|&p| dist(c, p) <= r + 1e-7));
+            let support = points
+                .iter()
+                .filter(|&&p| (dist(c, p) - r).abs() < 1e-7)
+                .count();
+            assert!(n == 1 || support >= 2);
+
+            // the optimum is determined by two or three of the points
+            let encloses = |(c, r): (PointF, f64)| points.iter().all(|&p| dist(c, p) <= r + 1e-7);
+            let mut best = if n == 1 { 0.0 } else { f64::MAX };
+            for i in 0..n {
+                for j in 0..i {
+                    let circle = diameter_circle(points[i], points[j]);
+                    if encloses(circle) {
+                        best = best.min(circle.1);
+                    }
+                    for k in 0..j {
+                        let circle = circumcircle(points[i], points[j], points[k]);
+                        if encloses(circle) {
+                            best = best.min(circle.1);
+                        }
+                    }
+                }
+            }
+            assert_close(r, best);
+        }
+    }
+}
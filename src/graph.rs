@@ -0,0 +1,326 @@
+//! Helpers for graphs given as edge lists, such as those read by `Scanner::scan_edges`, or implicitly
+//! by a function generating the neighbors of a state. `Graph` keeps the edge list with its input order
+//! for problems answering with edge indices.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Index of an edge in the order it was given to `Graph::new`.
+pub type EdgeId = usize;
+
+/// Directed graph over `0..n` storing each edge once, with out-edges of a vertex in input order.
+#[derive(Debug, Clone)]
+pub struct Graph<W> {
+    edges: Vec<(usize, usize, W)>,
+    out: Vec<Vec<EdgeId>>,
+}
+
+impl<W> Graph<W> {
+    /// Graph of `(u, v, w)` edges from `u` to `v`, where edge `i` gets id `i`.
+    pub fn new(n: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let mut out = vec![Vec::new(); n];
+        for (id, &(u, v, _)) in edges.iter().enumerate() {
+            assert!(
+                u < n && v < n,
+                "edge {} ({}, {}) out of range for {} vertices",
+                id,
+                u,
+                v,
+                n
+            );
+            out[u].push(id);
+        }
+        Self { edges, out }
+    }
+
+    /// Number of vertices.
+    pub fn len(&self) -> usize {
+        self.out.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.out.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Edges as `(id, u, v, w)` in id order.
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeId, usize, usize, &W)> + '_ {
+        self.edges
+            .iter()
+            .enumerate()
+            .map(|(id, (u, v, w))| (id, *u, *v, w))
+    }
+
+    pub fn edge_endpoints(&self, id: EdgeId) -> (usize, usize) {
+        let (u, v, _) = self.edges[id];
+        (u, v)
+    }
+
+    /// Edges leaving `u` as `(id, v, w)`.
+    pub fn out_edges(&self, u: usize) -> impl Iterator<Item = (EdgeId, usize, &W)> + '_ {
+        self.out[u].iter().map(move |&id| {
+            let (_, v, w) = &self.edges[id];
+            (id, *v, w)
+        })
+    }
+
+    /// Plain adjacency lists, as taken by `transitive_closure` or `dominator_tree`.
+    pub fn adjacency(&self) -> Vec<Vec<usize>> {
+        self.out
+            .iter()
+            .map(|ids| ids.iter().map(|&id| self.edges[id].1).collect())
+            .collect()
+    }
+}
+
+impl<W: Clone> Graph<W> {
+    /// Graph with every edge flipped, where edge `i` from `v` to `u` is edge `i` of `self` reversed.
+    pub fn reverse(&self) -> Self {
+        let edges = self.edges.iter().map(|(u, v, w)| (*v, *u, w.clone()));
+        Self::new(self.len(), edges.collect())
+    }
+}
+
+/// In-degree and out-degree of each vertex. For undirected graphs both count every incident edge.
+pub fn degrees(n: usize, edges: &[(usize, usize)], directed: bool) -> (Vec<usize>, Vec<usize>) {
+    let (mut indeg, mut outdeg) = (vec![0; n], vec![0; n]);
+    for &(u, v) in edges {
+        outdeg[u] += 1;
+        indeg[v] += 1;
+        if !directed {
+            outdeg[v] += 1;
+            indeg[u] += 1;
+        }
+    }
+    (indeg, outdeg)
+}
+
+/// Bitmask of the vertices reachable from each vertex by a non-empty path, for at most 64 vertices.
+/// A vertex reaches itself only through a cycle. Floyd-Warshall over bitsets in `O(n^2)`.
+pub fn transitive_closure(adj: &[Vec<usize>]) -> Vec<u64> {
+    let n = adj.len();
+    assert!(n <= 64, "{} vertices do not fit in a u64 bitmask", n);
+    let mut reach = adj
+        .iter()
+        .map(|edges| edges.iter().fold(0u64, |mask, &v| mask | 1 << v))
+        .collect::<Vec<_>>();
+    for k in 0..n {
+        let via = reach[k];
+        for mask in reach.iter_mut() {
+            if *mask >> k & 1 == 1 {
+                *mask |= via;
+            }
+        }
+    }
+    reach
+}
+
+/// Fewest steps from `start` to a state satisfying `is_goal`, exploring states as `neighbors`
+/// generates them, e.g. the positions of a puzzle. `None` if no goal is reachable.
+pub fn bfs_states<S, F>(start: S, is_goal: impl Fn(&S) -> bool, mut neighbors: F) -> Option<usize>
+where
+    S: Eq + Hash + Clone,
+    F: FnMut(&S) -> Vec<S>,
+{
+    let mut dist = HashMap::new();
+    dist.insert(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(s) = queue.pop_front() {
+        let d = dist[&s];
+        if is_goal(&s) {
+            return Some(d);
+        }
+        for t in neighbors(&s) {
+            if !dist.contains_key(&t) {
+                dist.insert(t.clone(), d + 1);
+                queue.push_back(t);
+            }
+        }
+    }
+    None
+}
+
+/// Same search as [`bfs_states`], returning the goal state reached together with the state each
+/// visited state was first reached from, to rebuild a shortest path with [`reconstruct_state_path`].
+pub fn bfs_state_parents<S, F>(
+    start: S,
+    is_goal: impl Fn(&S) -> bool,
+    mut neighbors: F,
+) -> Option<(S, HashMap<S, S>)>
+where
+    S: Eq + Hash + Clone,
+    F: FnMut(&S) -> Vec<S>,
+{
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start.clone()]);
+    while let Some(s) = queue.pop_front() {
+        if is_goal(&s) {
+            return Some((s, parents));
+        }
+        for t in neighbors(&s) {
+            if t != start && !parents.contains_key(&t) {
+                parents.insert(t.clone(), s.clone());
+                queue.push_back(t);
+            }
+        }
+    }
+    None
+}
+
+/// States from the start to `goal` inclusive, following `parents` back until a state without one.
+pub fn reconstruct_state_path<S: Eq + Hash + Clone>(parents: &HashMap<S, S>, goal: S) -> Vec<S> {
+    let mut path = vec![goal];
+    while let Some(prev) = parents.get(path.last().unwrap()) {
+        path.push(prev.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    #[test]
+    fn edge_ids_follow_input_order() {
+        let g = Graph::new(3, vec![(0, 1, 'a'), (2, 0, 'b'), (0, 2, 'c'), (0, 1, 'd')]);
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.edge_endpoints(1), (2, 0));
+        assert_eq!(
+            g.out_edges(0).collect::<Vec<_>>(),
+            vec![(0, 1, &'a'), (2, 2, &'c'), (3, 1, &'d')]
+        );
+        assert_eq!(g.adjacency(), vec![vec![1, 2, 1], vec![], vec![0]]);
+
+        let r = g.reverse();
+        for (id, u, v, w) in g.edges() {
+            assert_eq!(r.edge_endpoints(id), (v, u));
+            assert_eq!(r.edges().nth(id).unwrap().3, w);
+        }
+        assert_eq!(
+            r.out_edges(1).collect::<Vec<_>>(),
+            vec![(0, 0, &'a'), (3, 0, &'d')]
+        );
+    }
+
+    #[test]
+    fn reverse_twice_and_strong_components() {
+        // u and v share a strongly connected component iff each reaches the other
+        let components = |g: &Graph<u32>| {
+            let reach = transitive_closure(&g.adjacency());
+            (0..g.len())
+                .map(|u| {
+                    (0..g.len())
+                        .filter(|&v| v == u || reach[u] >> v & reach[v] >> u & 1 == 1)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut rng = XorShift64::new(98);
+        for _ in 0..50 {
+            let n = 1 + rng.gen_index(12);
+            let m = rng.gen_index(3 * n);
+            let edges = (0..m)
+                .map(|i| (rng.gen_index(n), rng.gen_index(n), i as u32))
+                .collect::<Vec<_>>();
+            let g = Graph::new(n, edges.clone());
+            let twice = g.reverse().reverse();
+            assert_eq!(twice.edges, edges);
+            assert_eq!(twice.adjacency(), g.adjacency());
+            assert_eq!(components(&g.reverse()), components(&g));
+        }
+    }
+
+    #[test]
+    fn directed_and_undirected_degrees() {
+        let edges = [(0, 1), (0, 2), (1, 2), (3, 0)];
+        let (indeg, outdeg) = degrees(4, &edges, true);
+        assert_eq!(indeg, vec![1, 1, 2, 0]);
+        assert_eq!(outdeg, vec![2, 1, 0, 1]);
+
+        let (indeg, outdeg) = degrees(4, &edges, false);
+        assert_eq!(indeg, vec![3, 2, 2, 1]);
+        assert_eq!(indeg, outdeg);
+    }
+
+    #[test]
+    fn closure_of_dag() {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3 -> 4, 5 isolated
+        let adj = vec![vec![1, 2], vec![3], vec![3], vec![4], vec![], vec![]];
+        let reach = transitive_closure(&adj);
+        assert_eq!(reach, vec![0b11110, 0b11000, 0b11000, 0b10000, 0, 0]);
+    }
+
+    #[test]
+    fn closure_with_cycle() {
+        let adj = vec![vec![1], vec![2], vec![0], vec![0]];
+        let reach = transitive_closure(&adj);
+        assert_eq!(reach, vec![0b111, 0b111, 0b111, 0b111]);
+        let full = (0..64).map(|v| vec![(v + 1) % 64]).collect::<Vec<_>>();
+        assert!(transitive_closure(&full).iter().all(|&m| m == u64::MAX));
+    }
+
+    #[test]
+    fn flip_adjacent_bits() {
+        // a move flips bits i and i + 1, which keeps the parity of the popcount
+        let flips = |s: &u8| (0..3).map(|i| s ^ (0b11 << i)).collect::<Vec<_>>();
+        assert_eq!(bfs_states(0u8, |&s| s == 0b1111, flips), Some(2));
+        assert_eq!(bfs_states(0u8, |&s| s == 0b1001, flips), Some(3));
+        assert_eq!(bfs_states(0u8, |&s| s == 0b0001, flips), None);
+        assert_eq!(bfs_states(5u8, |&s| s == 5, flips), Some(0));
+    }
+
+    #[test]
+    fn shortest_state_path() {
+        let flips = |s: &u8| (0..3).map(|i| s ^ (0b11 << i)).collect::<Vec<_>>();
+        for goal in 0..16u8 {
+            let found = bfs_state_parents(0u8, |&s| s == goal, flips);
+            let Some((reached, parents)) = found else {
+                assert_eq!(bfs_states(0u8, |&s| s == goal, flips), None);
+                continue;
+            };
+            assert_eq!(reached, goal);
+            let path = reconstruct_state_path(&parents, reached);
+            assert_eq!((path[0], *path.last().unwrap()), (0, goal));
+            assert_eq!(Some(path.len() - 1), bfs_states(0u8, |&s| s == goal, flips));
+            assert!(path.windows(2).all(|w| flips(&w[0]).contains(&w[1])));
+        }
+        let (reached, parents) = bfs_state_parents(6u8, |&s| s % 3 == 0, flips).unwrap();
+        assert_eq!(reconstruct_state_path(&parents, reached), vec![6]);
+    }
+
+    #[test]
+    fn sliding_puzzle() {
+        // 2x3 board with 0 as the blank, moved to a neighboring cell each step
+        let moves = |s: &[u8; 6]| {
+            let blank = s.iter().position(|&x| x == 0).unwrap();
+            let (r, c) = (blank / 3, blank % 3);
+            let mut next = Vec::new();
+            for (nr, nc) in [(r ^ 1, c), (r, c.wrapping_sub(1)), (r, c + 1)] {
+                if nc < 3 {
+                    let mut t = *s;
+                    t.swap(blank, nr * 3 + nc);
+                    next.push(t);
+                }
+            }
+            next
+        };
+        let goal = [1, 2, 3, 4, 5, 0];
+        assert_eq!(
+            bfs_states([1, 2, 3, 4, 0, 5], |s| *s == goal, moves),
+            Some(1)
+        );
+        assert_eq!(
+            bfs_states([4, 1, 2, 5, 0, 3], |s| *s == goal, moves),
+            Some(5)
+        );
+        assert_eq!(bfs_states([2, 1, 3, 4, 5, 0], |s| *s == goal, moves), None);
+    }
+}
@@ -0,0 +1,284 @@
+//! Helpers for character grids such as those read by `Scanner::scan_char_grid`.
+
+use std::collections::VecDeque;
+
+const NONE: usize = usize::MAX;
+
+/// Cell each reached cell was first reached from, `None` for the start and unreached cells.
+pub type Prev = Vec<Vec<Option<(usize, usize)>>>;
+
+/// Moves to the 4 neighbors as `(dr, dc, name)`.
+const MOVES: [(isize, isize, char); 4] = [(-1, 0, 'U'), (1, 0, 'D'), (0, -1, 'L'), (0, 1, 'R')];
+
+/// Fewest moves from `start` to each cell through passable cells, with the cells they come from to
+/// rebuild paths with [`reconstruct_path`]. Neighbors are tried in `U`, `D`, `L`, `R` order.
+pub fn grid_bfs(
+    grid: &[Vec<char>],
+    passable: impl Fn(char) -> bool,
+    start: (usize, usize),
+) -> (Vec<Vec<Option<usize>>>, Prev) {
+    let h = grid.len();
+    let w = grid.first().map_or(0, Vec::len);
+    let mut dist = vec![vec![None; w]; h];
+    let mut prev = vec![vec![None; w]; h];
+    if !passable(grid[start.0][start.1]) {
+        return (dist, prev);
+    }
+    dist[start.0][start.1] = Some(0);
+    let mut queue = VecDeque::from([start]);
+    while let Some((r, c)) = queue.pop_front() {
+        let d = dist[r][c].unwrap();
+        for (dr, dc, _) in MOVES {
+            let (nr, nc) = (r.wrapping_add_signed(dr), c.wrapping_add_signed(dc));
+            if nr < h && nc < w && dist[nr][nc].is_none() && passable(grid[nr][nc]) {
+                dist[nr][nc] = Some(d + 1);
+                prev[nr][nc] = Some((r, c));
+                queue.push_back((nr, nc));
+            }
+        }
+    }
+    (dist, prev)
+}
+
+/// Cells from `start` to `goal` inclusive following `prev` back from `goal`, or `None` if `goal`
+/// was not reached from `start`.
+pub fn reconstruct_path(
+    prev: &Prev,
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    let mut path = vec![goal];
+    let mut cell = goal;
+    while cell != start {
+        cell = prev[cell.0][cell.1]?;
+        path.push(cell);
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// `U`, `D`, `L` and `R` moves walking `path`, whose consecutive cells must be 4-neighbors.
+pub fn path_to_moves(path: &[(usize, usize)]) -> String {
+    path.windows(2)
+        .map(|step| {
+            let ((r, c), next) = (step[0], step[1]);
+            MOVES
+                .iter()
+                .find(|&&(dr, dc, _)| {
+                    (r.wrapping_add_signed(dr), c.wrapping_add_signed(dc)) == next
+                })
+                .map(|&(_, _, name)| name)
+                .unwrap_or_else(|| panic!("{:?} and {:?} are not adjacent", step[0], next))
+        })
+        .collect()
+}
+
+/// Connected components of the passable cells of a grid, labeled `0..count()` in row-major order of
+/// their first cell.
+pub struct GridComponents {
+    w: usize,
+    labels: Vec<usize>,
+    sizes: Vec<usize>,
+    boxes: Vec<((usize, usize), (usize, usize))>,
+}
+
+impl GridComponents {
+    pub fn label(&self, r: usize, c: usize) -> Option<usize> {
+        assert!(c < self.w, "column {} out of range for width {}", c, self.w);
+        Some(self.labels[r * self.w + c]).filter(|&l| l != NONE)
+    }
+
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Number of cells in the component.
+    pub fn size(&self, label: usize) -> usize {
+        self.sizes[label]
+    }
+
+    /// Inclusive corners `((r_min, c_min), (r_max, c_max))` of the smallest rectangle holding the component.
+    pub fn bounding_box(&self, label: usize) -> ((usize, usize), (usize, usize)) {
+        self.boxes[label]
+    }
+}
+
+/// Label the components of cells where `passable` holds, connecting 8 neighbors if `diagonal` and 4
+/// otherwise. Uses an explicit stack, so a 2000x2000 grid does not overflow the call stack.
+pub fn grid_components(
+    grid: &[Vec<char>],
+    passable: impl Fn(char) -> bool,
+    diagonal: bool,
+) -> GridComponents {
+    let h = grid.len();
+    let w = grid.first().map_or(0, Vec::len);
+    assert!(
+        grid.iter().all(|row| row.len() == w),
+        "grid rows must have the same length"
+    );
+    const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+    const ALL: [(isize, isize); 8] = [
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+    ];
+    let dirs: &[(isize, isize)] = if diagonal { &ALL } else { &ORTHOGONAL };
+
+    let mut components = GridComponents {
+        w,
+        labels: vec![NONE; h * w],
+        sizes: Vec::new(),
+        boxes: Vec::new(),
+    };
+    let mut stack = Vec::new();
+    for r in 0..h {
+        for c in 0..w {
+            if components.labels[r * w + c] != NONE || !passable(grid[r][c]) {
+                continue;
+            }
+            let label = components.count();
+            let (mut size, mut lo, mut hi) = (0, (r, c), (r, c));
+            components.labels[r * w + c] = label;
+            stack.push((r, c));
+            while let Some((r, c)) = stack.pop() {
+                size += 1;
+                lo = (lo.0.min(r), lo.1.min(c));
+                hi = (hi.0.max(r), hi.1.max(c));
+                for &(dr, dc) in dirs {
+                    let (nr, nc) = (r.wrapping_add_signed(dr), c.wrapping_add_signed(dc));
+                    if nr < h
+                        && nc < w
+                        && components.labels[nr * w + nc] == NONE
+                        && passable(grid[nr][nc])
+                    {
+                        components.labels[nr * w + nc] = label;
+                        stack.push((nr, nc));
+                    }
+                }
+            }
+            components.sizes.push(size);
+            components.boxes.push((lo, hi));
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    #[test]
+    fn nested_regions() {
+        let grid = parse(&[
+            ".......", //
+            ".#####.", //
+            ".#...#.", //
+            ".#.#.#.", //
+            ".#...#.", //
+            ".#####.", //
+        ]);
+        let open = grid_components(&grid, |c| c == '.', false);
+        assert_eq!(open.count(), 2);
+        assert_eq!(open.label(0, 0), Some(0));
+        assert_eq!(open.label(1, 1), None);
+        assert_eq!(open.size(0), 7 + 2 * 5);
+        assert_eq!(open.bounding_box(0), ((0, 0), (5, 6)));
+        let inner = open.label(2, 2).unwrap();
+        assert_eq!(open.size(inner), 8);
+        assert_eq!(open.bounding_box(inner), ((2, 2), (4, 4)));
+        assert_eq!(open.label(4, 4), Some(inner));
+
+        let walls = grid_components(&grid, |c| c == '#', false);
+        assert_eq!(walls.count(), 2);
+        assert_eq!(walls.size(walls.label(3, 3).unwrap()), 1);
+        assert_eq!(walls.bounding_box(0), ((1, 1), (5, 5)));
+    }
+
+    #[test]
+    fn diagonal_connectivity() {
+        let grid = parse(&[
+            "#..#", //
+            ".#.#", //
+            "..#.", //
+        ]);
+        let orthogonal = grid_components(&grid, |c| c == '#', false);
+        assert_eq!(orthogonal.count(), 4);
+        let diagonal = grid_components(&grid, |c| c == '#', true);
+        assert_eq!(diagonal.count(), 1);
+        assert_eq!(diagonal.size(0), 5);
+        assert_eq!(diagonal.bounding_box(0), ((0, 0), (2, 3)));
+    }
+
+    #[test]
+    fn blocked_grid() {
+        let grid = parse(&["###", "###"]);
+        let components = grid_components(&grid, |c| c == '.', true);
+        assert_eq!(components.count(), 0);
+        assert_eq!(components.label(1, 2), None);
+        assert_eq!(grid_components(&[], |_| true, false).count(), 0);
+    }
+
+    #[test]
+    fn large_grid_without_recursion() {
+        let n = 2000;
+        // a single snake shaped path through every other row
+        let grid = (0..n)
+            .map(|r| {
+                (0..n)
+                    .map(|c| {
+                        let open =
+                            r % 2 == 0 || (r % 4 == 1 && c == n - 1) || (r % 4 == 3 && c == 0);
+                        if open {
+                            '.'
+                        } else {
+                            '#'
+                        }
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<char>>>();
+        let components = grid_components(&grid, |c| c == '.', false);
+        assert_eq!(components.count(), 1);
+        assert_eq!(components.size(0), n / 2 * n + n / 2);
+    }
+
+    #[test]
+    fn shortest_path_moves() {
+        let grid = parse(&[
+            "S.#...", //
+            "#.#.#.", //
+            "#...#G", //
+        ]);
+        let (dist, prev) = grid_bfs(&grid, |c| c != '#', (0, 0));
+        assert_eq!(dist[2][5], Some(11));
+        let path = reconstruct_path(&prev, (0, 0), (2, 5)).unwrap();
+        assert_eq!(path.len(), 12);
+        assert_eq!(path_to_moves(&path), "RDDRRUURRDD");
+        assert_eq!(dist[1][0], None);
+    }
+
+    #[test]
+    fn unreachable_and_trivial_paths() {
+        let grid = parse(&[
+            "..#.", //
+            "..#.", //
+        ]);
+        let (dist, prev) = grid_bfs(&grid, |c| c == '.', (1, 1));
+        assert_eq!(dist[0][3], None);
+        assert_eq!(reconstruct_path(&prev, (1, 1), (0, 3)), None);
+
+        let path = reconstruct_path(&prev, (1, 1), (1, 1)).unwrap();
+        assert_eq!(path, vec![(1, 1)]);
+        assert_eq!(path_to_moves(&path), "");
+        assert_eq!(path_to_moves(&[]), "");
+    }
+}
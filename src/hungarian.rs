@@ -0,0 +1,184 @@
+//! Hungarian algorithm for the minimum cost assignment problem.
+
+/// Minimum cost assignment of every row to a distinct column, in `O(n^2 m)`.
+///
+/// `cost` is `n` x `m` with `n <= m`, and costs may be negative.
+/// Returns the total cost and the column assigned to each row.
+pub fn hungarian(cost: &[Vec<i64>]) -> (i64, Vec<usize>) {
+    let n = cost.len();
+    if n == 0 {
+        return (0, Vec::new());
+    }
+    let m = cost[0].len();
+    assert!(
+        cost.iter().all(|row| row.len() == m),
+        "rows must have the same length"
+    );
+    assert!(n <= m, "{} rows cannot be assigned to {} columns", n, m);
+
+    // 1-indexed potentials; column 0 is a virtual column holding the row being inserted
+    let (mut u, mut v) = (vec![0; n + 1], vec![0; m + 1]);
+    let mut row_of = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min = vec![i64::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let (mut delta, mut j1) = (i64::MAX, 0);
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let reduced = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if reduced < min[j] {
+                    min[j] = reduced;
+                    way[j] = j0;
+                }
+                if min[j] < delta {
+                    delta = min[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == 0 {
+                break;
+            }
+        }
+        // flip the augmenting path back to the virtual column
+        while j0 != 0 {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for j in 1..=m {
+        if row_of[j] != 0 {
+            assignment[row_of[j] - 1] = j - 1;
+        }
+    }
+    let total = (0..n).map(|i| cost[i][assignment[i]]).sum();
+    (total, assignment)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    /// Successive shortest paths on the bipartite network source -> rows -> columns -> sink.
+    fn min_cost_flow(cost: &[Vec<i64>]) -> i64 {
+        let (n, m) = (cost.len(), cost[0].len());
+        let (source, sink) = (n + m, n + m + 1);
+        // (to, capacity, cost, index of the reverse edge)
+        let mut graph = vec![Vec::<(usize, i64, i64, usize)>::new(); n + m + 2];
+        let mut add_edge = |from: usize, to: usize, c: i64| {
+            let (rf, rt) = (graph[to].len(), graph[from].len());
+            graph[from].push((to, 1, c, rf));
+            graph[to].push((from, 0, -c, rt));
+        };
+        for (i, row) in cost.iter().enumerate() {
+            add_edge(source, i, 0);
+            for (j, &c) in row.iter().enumerate() {
+                add_edge(i, n + j, c);
+            }
+        }
+        for j in 0..m {
+            add_edge(n + j, sink, 0);
+        }
+
+        let mut total = 0;
+        for _ in 0..n {
+            // Bellman-Ford handles the negative costs
+            let mut dist = vec![i64::MAX; n + m + 2];
+            let mut prev = vec![(0, 0); n + m + 2];
+            dist[source] = 0;
+            let mut updated = true;
+            while updated {
+                updated = false;
+                for from in 0..graph.len() {
+                    if dist[from] == i64::MAX {
+                        continue;
+                    }
+                    for (k, &(to, cap, c, _)) in graph[from].iter().enumerate() {
+                        if cap > 0 && dist[from] + c < dist[to] {
+                            dist[to] = dist[from] + c;
+                            prev[to] = (from, k);
+                            updated = true;
+                        }
+                    }
+                }
+            }
+            total += dist[sink];
+            let mut v = sink;
+            while v != source {
+                let (from, k) = prev[v];
+                graph[from][k].1 -= 1;
+                let rev = graph[from][k].3;
+                graph[v][rev].1 += 1;
+                v = from;
+            }
+        }
+        total
+    }
+
+    fn check(cost: &[Vec<i64>], expected: i64) {
+        let (total, assignment) = hungarian(cost);
+        assert_eq!(total, expected);
+        let mut columns = assignment.clone();
+        columns.sort_unstable();
+        columns.dedup();
+        assert_eq!(columns.len(), cost.len());
+        let sum = assignment
+            .iter()
+            .enumerate()
+            .map(|(i, &j)| cost[i][j])
+            .sum::<i64>();
+        assert_eq!(sum, total);
+    }
+
+    #[test]
+    fn small_instances() {
+        check(&[vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]], 5);
+        check(&[vec![1, 2, 3], vec![2, 4, 6], vec![3, 6, 9]], 10);
+        check(&[vec![-5, 0, 0], vec![0, -5, 0], vec![0, 0, -5]], -15);
+        check(&[], 0);
+    }
+
+    #[test]
+    fn rectangular() {
+        let cost = vec![vec![7, 3, 9, 1], vec![2, 8, 1, 6]];
+        check(&cost, 2);
+        assert_eq!(hungarian(&cost).1, vec![3, 2]);
+    }
+
+    #[test]
+    fn matches_min_cost_flow() {
+        let mut rng = XorShift64::new(21);
+        for _ in 0..20 {
+            let n = 20;
+            let m = n + rng.gen_index(5);
+            let cost = (0..n)
+                .map(|_| {
+                    (0..m)
+                        .map(|_| rng.gen_range(0..2001) as i64 - 1000)
+                        .collect()
+                })
+                .collect::<Vec<Vec<i64>>>();
+            check(&cost, min_cost_flow(&cost));
+        }
+    }
+}
@@ -0,0 +1,37 @@
+pub mod algo;
+pub mod bit;
+pub mod cio;
+pub mod coloring;
+pub mod convolution;
+pub mod date;
+pub mod dominator;
+pub mod dual_segtree;
+pub mod dynamic_segtree;
+pub mod eval;
+pub mod fenwick;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod heuristic;
+pub mod hungarian;
+pub mod math;
+pub mod min_cut;
+pub mod mo;
+pub mod palindromic_tree;
+pub mod prefix;
+pub mod random;
+pub mod recurrence;
+pub mod samples;
+pub mod search;
+pub mod segtree;
+pub mod sequence;
+pub mod spanning_tree;
+pub mod sqrt_decomp;
+pub mod stress;
+pub mod string;
+pub mod suffix_automaton;
+pub mod tree;
+pub mod union_find;
+pub mod validate;
+
+mod range;
@@ -0,0 +1,96 @@
+//! Modular arithmetic helpers. Products are taken in `u128`, so any `u64` modulus works.
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `a^e mod m`.
+pub(crate) fn pow_mod(mut a: u64, mut e: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    a %= m;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, a, m);
+        }
+        a = mul_mod(a, a, m);
+        e >>= 1;
+    }
+    result
+}
+
+/// `i! mod modulus` for `i` in `0..=n`.
+pub fn factorials_mod(n: usize, modulus: u64) -> Vec<u64> {
+    let mut fact = Vec::with_capacity(n + 1);
+    fact.push(1 % modulus);
+    for i in 1..=n {
+        fact.push(mul_mod(fact[i - 1], i as u64, modulus));
+    }
+    fact
+}
+
+/// `1 / i! mod modulus` for `i` in `0..=n`, with `n < modulus` for the prime `modulus`.
+/// Only `n!` is inverted, the rest follow backward from `1 / (i - 1)! = i / i!`.
+pub fn inverse_factorials_mod(n: usize, modulus: u64) -> Vec<u64> {
+    assert!((n as u64) < modulus, "{}! is 0 modulo {}", n, modulus);
+    let mut inv = vec![0; n + 1];
+    inv[n] = pow_mod(factorials_mod(n, modulus)[n], modulus - 2, modulus);
+    for i in (1..=n).rev() {
+        inv[i - 1] = mul_mod(inv[i], i as u64, modulus);
+    }
+    inv
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn comb_from_factorials() {
+        let m = 1_000_000_007;
+        let n = 30;
+        let (fact, inv) = (factorials_mod(n, m), inverse_factorials_mod(n, m));
+        assert_eq!(&fact[..6], &[1, 1, 2, 6, 24, 120]);
+        assert!((0..=n).all(|i| fact[i] * inv[i] % m == 1));
+
+        // Pascal's triangle
+        let mut pascal = vec![vec![1u64]];
+        for i in 1..=n {
+            let prev = &pascal[i - 1];
+            let row = (0..=i)
+                .map(|k| {
+                    let left = if k > 0 { prev[k - 1] } else { 0 };
+                    (left + prev.get(k).copied().unwrap_or(0)) % m
+                })
+                .collect();
+            pascal.push(row);
+        }
+        for i in 0..=n {
+            for k in 0..=i {
+                let comb = fact[i] * inv[k] % m * inv[i - k] % m;
+                assert_eq!(comb, pascal[i][k]);
+            }
+        }
+    }
+
+    #[test]
+    fn small_prime() {
+        assert_eq!(factorials_mod(6, 7), vec![1, 1, 2, 6, 3, 1, 6]);
+        assert_eq!(inverse_factorials_mod(6, 7), vec![1, 1, 4, 6, 5, 1, 6]);
+        assert_eq!(factorials_mod(0, 7), vec![1]);
+    }
+
+    #[test]
+    fn modulus_above_u32() {
+        // 2^61 - 1 is prime
+        let m = (1 << 61) - 1;
+        assert_eq!(pow_mod(2, 61, m), 1);
+        assert_eq!(pow_mod(m - 1, 2, m), 1);
+        assert_eq!(pow_mod(5, 0, 1), 0);
+        let n = 25;
+        let (fact, inv) = (factorials_mod(n, m), inverse_factorials_mod(n, m));
+        // 19! < 2^61 - 1 < 20!, so 20! wraps once
+        assert_eq!(fact[19], 121_645_100_408_832_000);
+        assert_eq!(fact[20], 2_432_902_008_176_640_000 - m);
+        assert!((0..=n).all(|i| mul_mod(fact[i], inv[i], m) == 1));
+    }
+}
@@ -0,0 +1,128 @@
+//! Global minimum cut of an undirected weighted graph.
+
+/// Stoer-Wagner in `O(n^3)`: the minimum total weight of edges separating the vertices into two
+/// non-empty sides, and the vertices of one side.
+///
+/// Weights must be non-negative. Parallel edges are summed and self loops ignored.
+pub fn stoer_wagner(n: usize, edges: &[(usize, usize, i64)]) -> (i64, Vec<usize>) {
+    assert!(n >= 2, "a cut needs at least two vertices");
+    let mut weight = vec![vec![0; n]; n];
+    for &(u, v, w) in edges {
+        assert!(w >= 0, "negative weight {} on {}-{}", w, u, v);
+        if u != v {
+            weight[u][v] += w;
+            weight[v][u] += w;
+        }
+    }
+    // vertices merged into each remaining super vertex
+    let mut members = (0..n).map(|v| vec![v]).collect::<Vec<_>>();
+    let mut alive = (0..n).collect::<Vec<_>>();
+    let mut best = (i64::MAX, Vec::new());
+
+    while alive.len() > 1 {
+        // maximum adjacency order; the last two vertices give a minimum s-t cut
+        let mut connectivity = vec![0; n];
+        let mut added = vec![false; n];
+        let (mut prev, mut last) = (alive[0], alive[0]);
+        for _ in 0..alive.len() {
+            let next = *alive
+                .iter()
+                .filter(|&&v| !added[v])
+                .max_by_key(|&&v| connectivity[v])
+                .unwrap();
+            added[next] = true;
+            prev = last;
+            last = next;
+            for &v in &alive {
+                connectivity[v] += weight[next][v];
+            }
+        }
+        if connectivity[last] < best.0 {
+            best = (connectivity[last], members[last].clone());
+        }
+
+        let merged = std::mem::take(&mut members[last]);
+        members[prev].extend(merged);
+        for &v in &alive {
+            weight[prev][v] += weight[last][v];
+            weight[v][prev] = weight[prev][v];
+        }
+        weight[prev][prev] = 0;
+        alive.retain(|&v| v != last);
+    }
+    best.1.sort_unstable();
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    fn cut_weight(edges: &[(usize, usize, i64)], side: &[bool]) -> i64 {
+        edges
+            .iter()
+            .filter(|&&(u, v, _)| side[u] != side[v])
+            .map(|&(_, _, w)| w)
+            .sum()
+    }
+
+    fn check(n: usize, edges: &[(usize, usize, i64)], expected: i64) {
+        let (weight, side) = stoer_wagner(n, edges);
+        assert_eq!(weight, expected);
+        assert!(!side.is_empty() && side.len() < n);
+        let mut in_side = vec![false; n];
+        side.iter().for_each(|&v| in_side[v] = true);
+        assert_eq!(cut_weight(edges, &in_side), weight);
+    }
+
+    #[test]
+    fn bottleneck() {
+        // two heavy triangles joined by a single light edge
+        let edges = [
+            (0, 1, 10),
+            (1, 2, 10),
+            (2, 0, 10),
+            (3, 4, 10),
+            (4, 5, 10),
+            (5, 3, 10),
+            (2, 3, 1),
+        ];
+        check(6, &edges, 1);
+        let side = stoer_wagner(6, &edges).1;
+        assert!(side == vec![0, 1, 2] || side == vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn complete_graph() {
+        let n = 7;
+        let edges = (0..n)
+            .flat_map(|u| (u + 1..n).map(move |v| (u, v, 3)))
+            .collect::<Vec<_>>();
+        // isolating one vertex cuts its n - 1 edges
+        check(n, &edges, 3 * (n as i64 - 1));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = XorShift64::new(23);
+        for _ in 0..100 {
+            let n = 2 + rng.gen_index(11);
+            let m = rng.gen_index(3 * n);
+            let edges = (0..m)
+                .map(|_| {
+                    let w = rng.gen_range(0..20) as i64;
+                    (rng.gen_index(n), rng.gen_index(n), w)
+                })
+                .collect::<Vec<_>>();
+            let expected = (1..(1 << n) - 1)
+                .map(|mask: usize| {
+                    let side = (0..n).map(|v| mask >> v & 1 == 1).collect::<Vec<_>>();
+                    cut_weight(&edges, &side)
+                })
+                .min()
+                .unwrap();
+            check(n, &edges, expected);
+        }
+    }
+}
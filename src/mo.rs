@@ -0,0 +1,221 @@
+//! Mo's algorithm for answering offline range queries by moving a window.
+
+/// Window state maintained while the range `[l, r)` moves between queries.
+pub trait MoState {
+    type Answer;
+
+    fn add(&mut self, i: usize);
+    fn remove(&mut self, i: usize);
+    fn answer(&self) -> Self::Answer;
+
+    fn add_left(&mut self, i: usize) {
+        self.add(i);
+    }
+    fn add_right(&mut self, i: usize) {
+        self.add(i);
+    }
+    fn remove_left(&mut self, i: usize) {
+        self.remove(i);
+    }
+    fn remove_right(&mut self, i: usize) {
+        self.remove(i);
+    }
+}
+
+/// Classic ordering: sort by `sqrt(n)` sized block of `l`, alternating the direction of `r` between blocks.
+pub fn mo_order_block(queries: &[(usize, usize)], n: usize) -> Vec<usize> {
+    let block = ((n as f64).sqrt() as usize).max(1);
+    let mut order = (0..queries.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| {
+        let (l, r) = queries[i];
+        let b = l / block;
+        (b, if b % 2 == 0 { r } else { usize::MAX - r })
+    });
+    order
+}
+
+/// Ordering along a Hilbert curve over the `(l, r)` plane, which keeps consecutive queries close.
+pub fn mo_order_hilbert(queries: &[(usize, usize)], n: usize) -> Vec<usize> {
+    let side = (n + 1).next_power_of_two();
+    let keys = queries
+        .iter()
+        .map(|&(l, r)| hilbert_index(l, r, side))
+        .collect::<Vec<_>>();
+    let mut order = (0..queries.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| keys[i]);
+    order
+}
+
+/// Position of `(x, y)` along the Hilbert curve filling a `side` x `side` square.
+fn hilbert_index(x: usize, y: usize, side: usize) -> u64 {
+    let (mut x, mut y) = (x as u64, y as u64);
+    let mut d = 0;
+    let mut s = side as u64 / 2;
+    while s > 0 {
+        let rx = u64::from(x & s > 0);
+        let ry = u64::from(y & s > 0);
+        d += s * s * ((3 * rx) ^ ry);
+        // rotate the quadrant so the curve stays continuous
+        if ry == 0 {
+            if rx == 1 {
+                x = s - 1 - (x & (s - 1));
+                y = s - 1 - (y & (s - 1));
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        x &= s - 1;
+        y &= s - 1;
+        s /= 2;
+    }
+    d
+}
+
+/// Answer half open range queries `[l, r)` over `0..n`, visiting them in the order chosen by `ordering`.
+pub fn mo_solve<S: MoState>(
+    state: &mut S,
+    n: usize,
+    queries: &[(usize, usize)],
+    ordering: impl Fn(&[(usize, usize)], usize) -> Vec<usize>,
+) -> Vec<S::Answer> {
+    let mut answers = (0..queries.len()).map(|_| None).collect::<Vec<_>>();
+    let (mut l, mut r) = (0, 0);
+    for i in ordering(queries, n) {
+        let (ql, qr) = queries[i];
+        assert!(ql <= qr && qr <= n, "invalid query {}..{}", ql, qr);
+        // grow before shrinking so the window never becomes inverted
+        while l > ql {
+            l -= 1;
+            state.add_left(l);
+        }
+        while r < qr {
+            state.add_right(r);
+            r += 1;
+        }
+        while l < ql {
+            state.remove_left(l);
+            l += 1;
+        }
+        while r > qr {
+            r -= 1;
+            state.remove_right(r);
+        }
+        answers[i] = Some(state.answer());
+    }
+    answers.into_iter().map(Option::unwrap).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    struct Distinct<'a> {
+        values: &'a [usize],
+        count: Vec<usize>,
+        distinct: usize,
+        moves: usize,
+    }
+
+    impl<'a> Distinct<'a> {
+        fn new(values: &'a [usize]) -> Self {
+            let max = values.iter().copied().max().unwrap_or(0);
+            Self {
+                values,
+                count: vec![0; max + 1],
+                distinct: 0,
+                moves: 0,
+            }
+        }
+    }
+
+    impl MoState for Distinct<'_> {
+        type Answer = usize;
+
+        fn add(&mut self, i: usize) {
+            self.moves += 1;
+            let v = self.values[i];
+            if self.count[v] == 0 {
+                self.distinct += 1;
+            }
+            self.count[v] += 1;
+        }
+
+        fn remove(&mut self, i: usize) {
+            self.moves += 1;
+            let v = self.values[i];
+            self.count[v] -= 1;
+            if self.count[v] == 0 {
+                self.distinct -= 1;
+            }
+        }
+
+        fn answer(&self) -> usize {
+            self.distinct
+        }
+    }
+
+    fn random_queries(rng: &mut XorShift64, n: usize, q: usize) -> Vec<(usize, usize)> {
+        (0..q)
+            .map(|_| {
+                let a = rng.gen_index(n + 1);
+                let b = rng.gen_index(n + 1);
+                (a.min(b), a.max(b))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn distinct_count_matches_brute_force() {
+        let mut rng = XorShift64::new(13);
+        let n = 200;
+        let values = (0..n).map(|_| rng.gen_index(20)).collect::<Vec<_>>();
+        let queries = random_queries(&mut rng, n, 300);
+        let expected = queries
+            .iter()
+            .map(|&(l, r)| {
+                let mut seen = values[l..r].to_vec();
+                seen.sort_unstable();
+                seen.dedup();
+                seen.len()
+            })
+            .collect::<Vec<_>>();
+
+        let block = mo_solve(&mut Distinct::new(&values), n, &queries, mo_order_block);
+        let hilbert = mo_solve(&mut Distinct::new(&values), n, &queries, mo_order_hilbert);
+        assert_eq!(block, expected);
+        assert_eq!(hilbert, expected);
+    }
+
+    #[test]
+    fn hilbert_moves_less() {
+        let mut rng = XorShift64::new(10_000);
+        let n = 100_000;
+        let values = (0..n).map(|_| rng.gen_index(1000)).collect::<Vec<_>>();
+        let queries = random_queries(&mut rng, n, 10_000);
+
+        let mut block = Distinct::new(&values);
+        let block_answers = mo_solve(&mut block, n, &queries, mo_order_block);
+        let mut hilbert = Distinct::new(&values);
+        let hilbert_answers = mo_solve(&mut hilbert, n, &queries, mo_order_hilbert);
+        assert_eq!(block_answers, hilbert_answers);
+        assert!(
+            hilbert.moves < block.moves,
+            "hilbert {} block {}",
+            hilbert.moves,
+            block.moves
+        );
+    }
+
+    #[test]
+    fn hilbert_index_is_a_bijection() {
+        let side = 8;
+        let mut seen = vec![false; side * side];
+        for x in 0..side {
+            for y in 0..side {
+                let d = hilbert_index(x, y, side) as usize;
+                assert!(!seen[d]);
+                seen[d] = true;
+            }
+        }
+    }
+}
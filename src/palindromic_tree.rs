@@ -0,0 +1,202 @@
+//! Palindromic tree (eertree): one node per distinct palindromic substring.
+
+use std::{collections::BTreeMap, ops::Range};
+
+#[derive(Debug, Clone)]
+struct Node {
+    /// `-1` for the imaginary root.
+    len: isize,
+    /// Longest proper palindromic suffix.
+    link: usize,
+    next: BTreeMap<u8, usize>,
+    /// Number of positions where this is the longest palindromic suffix.
+    count: u64,
+    /// End (exclusive) of the first occurrence.
+    first_end: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct PalindromicTree {
+    s: Vec<u8>,
+    nodes: Vec<Node>,
+    /// Longest palindromic suffix of `s`.
+    last: usize,
+}
+
+impl Default for PalindromicTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PalindromicTree {
+    const IMAGINARY: usize = 0;
+    const EMPTY: usize = 1;
+
+    pub fn new() -> Self {
+        let root = |len| Node {
+            len,
+            link: Self::IMAGINARY,
+            next: BTreeMap::new(),
+            count: 0,
+            first_end: 0,
+        };
+        Self {
+            s: Vec::new(),
+            nodes: vec![root(-1), root(0)],
+            last: Self::EMPTY,
+        }
+    }
+
+    pub fn build(s: &[u8]) -> Self {
+        let mut tree = Self::new();
+        for &c in s {
+            tree.push(c);
+        }
+        tree
+    }
+
+    /// Longest palindrome suffix reachable from `v` by links that extends to `c` at the end of `s`.
+    fn extendable(&self, mut v: usize) -> usize {
+        let i = self.s.len() - 1;
+        loop {
+            let j = i as isize - self.nodes[v].len - 1;
+            if j >= 0 && self.s[j as usize] == self.s[i] {
+                return v;
+            }
+            v = self.nodes[v].link;
+        }
+    }
+
+    /// Append `c`, returning whether a new distinct palindrome appeared.
+    pub fn push(&mut self, c: u8) -> bool {
+        self.s.push(c);
+        let cur = self.extendable(self.last);
+        if let Some(&v) = self.nodes[cur].next.get(&c) {
+            self.last = v;
+            self.nodes[v].count += 1;
+            return false;
+        }
+        let len = self.nodes[cur].len + 2;
+        let link = if len == 1 {
+            Self::EMPTY
+        } else {
+            let w = self.extendable(self.nodes[cur].link);
+            self.nodes[w].next[&c]
+        };
+        let v = self.nodes.len();
+        self.nodes.push(Node {
+            len,
+            link,
+            next: BTreeMap::new(),
+            count: 1,
+            first_end: self.s.len(),
+        });
+        self.nodes[cur].next.insert(c, v);
+        self.last = v;
+        true
+    }
+
+    pub fn distinct_palindromes(&self) -> usize {
+        self.nodes.len() - 2
+    }
+
+    /// Each distinct palindrome as the range of its first occurrence, with its number of occurrences.
+    pub fn occurrence_counts(&self) -> Vec<(Range<usize>, u64)> {
+        let mut count = self.nodes.iter().map(|node| node.count).collect::<Vec<_>>();
+        // links point to earlier nodes, so reverse creation order is a valid topological order
+        for v in (2..self.nodes.len()).rev() {
+            count[self.nodes[v].link] += count[v];
+        }
+        (2..self.nodes.len())
+            .map(|v| {
+                let node = &self.nodes[v];
+                (node.first_end - node.len as usize..node.first_end, count[v])
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+    use std::collections::HashMap;
+
+    fn brute_force(s: &[u8]) -> HashMap<&[u8], u64> {
+        let mut counts = HashMap::new();
+        for i in 0..s.len() {
+            for j in i + 1..=s.len() {
+                let t = &s[i..j];
+                if t.iter().eq(t.iter().rev()) {
+                    *counts.entry(t).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    fn as_map<'a>(s: &'a [u8], tree: &PalindromicTree) -> HashMap<&'a [u8], u64> {
+        tree.occurrence_counts()
+            .into_iter()
+            .map(|(range, count)| (&s[range], count))
+            .collect()
+    }
+
+    #[test]
+    fn abacaba() {
+        let s = b"abacaba";
+        let tree = PalindromicTree::build(s);
+        assert_eq!(tree.distinct_palindromes(), 7);
+        let counts = as_map(s, &tree);
+        let expected = [
+            ("a", 4),
+            ("b", 2),
+            ("c", 1),
+            ("aba", 2),
+            ("aca", 1),
+            ("bacab", 1),
+            ("abacaba", 1),
+        ]
+        .into_iter()
+        .map(|(p, c)| (p.as_bytes(), c))
+        .collect::<HashMap<_, _>>();
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn no_repeated_characters() {
+        let tree = PalindromicTree::build(b"abcdef");
+        assert_eq!(tree.distinct_palindromes(), 6);
+        assert!(tree
+            .occurrence_counts()
+            .iter()
+            .all(|(r, c)| r.len() == 1 && *c == 1));
+    }
+
+    #[test]
+    fn online_push() {
+        let mut tree = PalindromicTree::new();
+        let new = b"abcab".iter().map(|&c| tree.push(c)).collect::<Vec<_>>();
+        assert_eq!(new, vec![true, true, true, false, false]);
+        assert_eq!(tree.distinct_palindromes(), 3);
+        assert!(tree.push(b'a'));
+        assert_eq!(tree.distinct_palindromes(), 4);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = XorShift64::new(19);
+        for _ in 0..50 {
+            let n = rng.gen_index(201);
+            let alphabet = 1 + rng.gen_range(0..3);
+            let s = (0..n)
+                .map(|_| b'a' + rng.gen_range(0..alphabet) as u8)
+                .collect::<Vec<_>>();
+            let tree = PalindromicTree::build(&s);
+            let expected = brute_force(&s);
+            assert_eq!(tree.distinct_palindromes(), expected.len());
+            assert_eq!(as_map(&s, &tree), expected);
+        }
+    }
+}
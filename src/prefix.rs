@@ -0,0 +1,132 @@
+//! Prefix folds for range queries without updates, e.g. range xor in `O(1)` without a segment tree.
+
+use std::ops::RangeBounds;
+
+use crate::range::bounds;
+
+pub struct PrefixFold<T> {
+    /// `prefix[i]` is the fold of the first `i` values.
+    prefix: Vec<T>,
+    /// `inverse(fold(0..r), fold(0..l))` is `fold(l..r)`, if the operation has one.
+    inverse: Option<fn(&T, &T) -> T>,
+}
+
+pub type PrefixXor = PrefixFold<u64>;
+pub type PrefixSum = PrefixFold<i64>;
+
+impl<T: Clone> PrefixFold<T> {
+    /// Folds of a non-invertible operation such as gcd or max, so only prefixes can be folded.
+    pub fn new(values: &[T], identity: T, op: impl Fn(&T, &T) -> T) -> Self {
+        let mut prefix = Vec::with_capacity(values.len() + 1);
+        prefix.push(identity);
+        for v in values {
+            prefix.push(op(prefix.last().unwrap(), v));
+        }
+        Self {
+            prefix,
+            inverse: None,
+        }
+    }
+
+    /// Folds of a group operation, where `inverse(a, b)` removes `b` folded in front of `a`, so any
+    /// range can be folded.
+    pub fn with_inverse(
+        values: &[T],
+        identity: T,
+        op: impl Fn(&T, &T) -> T,
+        inverse: fn(&T, &T) -> T,
+    ) -> Self {
+        Self {
+            inverse: Some(inverse),
+            ..Self::new(values, identity, op)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fold of the first `end` values.
+    pub fn prefix(&self, end: usize) -> T {
+        self.prefix[end].clone()
+    }
+
+    /// Fold of the values in `range`. Panics for a range not starting at `0` without an inverse.
+    pub fn fold(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = bounds(range, self.len());
+        if l == 0 {
+            return self.prefix(r);
+        }
+        let inverse = self.inverse.unwrap_or_else(|| {
+            panic!(
+                "fold of {}..{} needs an invertible operation, only prefixes can be folded",
+                l, r
+            )
+        });
+        inverse(&self.prefix[r], &self.prefix[l])
+    }
+}
+
+impl PrefixXor {
+    pub fn xor(values: &[u64]) -> Self {
+        Self::with_inverse(values, 0, |a, b| a ^ b, |a, b| a ^ b)
+    }
+}
+
+impl PrefixSum {
+    pub fn sum(values: &[i64]) -> Self {
+        Self::with_inverse(values, 0, |a, b| a + b, |a, b| a - b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{random::XorShift64, segtree::SegmentTree};
+
+    #[test]
+    fn matches_segment_tree() {
+        let mut rng = XorShift64::new(94);
+        let n = 200;
+        let a = (0..n).map(|_| rng.next_u64()).collect::<Vec<_>>();
+        let b = (0..n)
+            .map(|_| rng.gen_range(0..2_000_000) as i64 - 1_000_000)
+            .collect::<Vec<_>>();
+        let (xors, sums) = (PrefixXor::xor(&a), PrefixSum::sum(&b));
+        let xor_tree = SegmentTree::from_vec(a, 0, |x, y| x ^ y);
+        let sum_tree = SegmentTree::from_vec(b, 0, |x, y| x + y);
+        for _ in 0..1000 {
+            let l = rng.gen_index(n + 1);
+            let r = l + rng.gen_index(n + 1 - l);
+            assert_eq!(xors.fold(l..r), xor_tree.query(l..r));
+            assert_eq!(sums.fold(l..r), sum_tree.query(l..r));
+        }
+        assert_eq!(sums.fold(..), sum_tree.query(..));
+    }
+
+    #[test]
+    fn prefixes_of_non_invertible_fold() {
+        let max = PrefixFold::new(&[3, 1, 4, 1, 5], i64::MIN, |a, b| *a.max(b));
+        assert_eq!(max.prefix(0), i64::MIN);
+        assert_eq!(max.prefix(3), 4);
+        assert_eq!(max.fold(..2), 3);
+        assert_eq!(max.fold(..), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "fold of 1..3 needs an invertible operation")]
+    fn non_invertible_range_panics() {
+        let gcd = PrefixFold::new(&[12u64, 18, 8], 0, |a, b| {
+            let (mut a, mut b) = (*a, *b);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        });
+        gcd.fold(1..3);
+    }
+}
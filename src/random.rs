@@ -0,0 +1,242 @@
+//! Deterministic pseudo random number generation for randomized algorithms and tests.
+
+use std::ops::Range;
+
+/// Marsaglia's xorshift64. Small and fast, which is all a contest needs.
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> Self {
+        // The all zero state is a fixed point.
+        let state = if seed == 0 {
+            0x9e37_79b9_7f4a_7c15
+        } else {
+            seed
+        };
+        Self { state }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Uniform value in `range`. The modulo bias is negligible for contest sized ranges.
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "empty range");
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// Uniform index in `0..n`.
+    pub fn gen_index(&mut self, n: usize) -> usize {
+        self.gen_range(0..n as u64) as usize
+    }
+
+    /// Uniform value in `[0, 1)`.
+    pub fn gen_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, a: &mut [T]) {
+        for i in (1..a.len()).rev() {
+            let j = self.gen_index(i + 1);
+            a.swap(i, j);
+        }
+    }
+
+    /// Shuffle so that `a[..k]` is a uniform random selection of `k` elements in random order,
+    /// leaving the rest in no particular order. Costs `O(k)`.
+    pub fn shuffle_partial<T>(&mut self, a: &mut [T], k: usize) {
+        assert!(k <= a.len(), "k {} exceeds length {}", k, a.len());
+        for i in 0..k {
+            let j = i + self.gen_index(a.len() - i);
+            a.swap(i, j);
+        }
+    }
+
+    /// `k` uniformly chosen items of `iter` in one pass, or all of them if there are fewer.
+    pub fn reservoir_sample<T>(&mut self, iter: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+        let mut reservoir = Vec::with_capacity(k);
+        for (i, item) in iter.into_iter().enumerate() {
+            if i < k {
+                reservoir.push(item);
+            } else {
+                let j = self.gen_index(i + 1);
+                if j < k {
+                    reservoir[j] = item;
+                }
+            }
+        }
+        reservoir
+    }
+}
+
+/// Draws index `i` with probability proportional to `weights[i]` in `O(1)` by Vose's alias method.
+#[derive(Debug, Clone)]
+pub struct WeightedIndex {
+    /// Probability of keeping the uniformly drawn column instead of taking its alias.
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl WeightedIndex {
+    /// Weights must be finite and non-negative with a positive sum. Zero weights are never drawn.
+    pub fn new(weights: &[f64]) -> Self {
+        assert!(
+            weights.iter().all(|w| w.is_finite() && *w >= 0.0),
+            "weights must be finite and non-negative"
+        );
+        let total = weights.iter().sum::<f64>();
+        assert!(total > 0.0, "weights must have a positive sum");
+        let n = weights.len();
+        let mut scaled = weights
+            .iter()
+            .map(|w| w * n as f64 / total)
+            .collect::<Vec<_>>();
+        let (mut small, mut large): (Vec<_>, Vec<_>) = (0..n).partition(|&i| scaled[i] < 1.0);
+        let mut prob = vec![1.0; n];
+        let mut alias = (0..n).collect::<Vec<_>>();
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            prob[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] -= 1.0 - scaled[s];
+            if scaled[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // left over only by rounding, so they are (almost) full columns, except true zeros
+        let heaviest = (0..n)
+            .max_by(|&a, &b| weights[a].total_cmp(&weights[b]))
+            .unwrap();
+        for s in small {
+            if weights[s] == 0.0 {
+                prob[s] = 0.0;
+                alias[s] = heaviest;
+            }
+        }
+        Self { prob, alias }
+    }
+
+    pub fn sample(&self, rng: &mut XorShift64) -> usize {
+        let i = rng.gen_index(self.prob.len());
+        if rng.gen_f64() < self.prob[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gen_range_in_bounds() {
+        let mut rng = XorShift64::new(1);
+        for _ in 0..1000 {
+            let v = rng.gen_range(3..7);
+            assert!((3..7).contains(&v));
+            let f = rng.gen_f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+    }
+
+    #[test]
+    fn shuffle_is_permutation() {
+        let mut rng = XorShift64::new(42);
+        let mut a = (0..100).collect::<Vec<_>>();
+        rng.shuffle(&mut a);
+        let mut sorted = a.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn weighted_frequencies() {
+        let mut rng = XorShift64::new(79);
+        let weighted = WeightedIndex::new(&[1.0, 2.0, 7.0]);
+        let draws = 100_000;
+        let mut counts = [0; 3];
+        for _ in 0..draws {
+            counts[weighted.sample(&mut rng)] += 1;
+        }
+        for (count, p) in counts.into_iter().zip([0.1, 0.2, 0.7]) {
+            assert!(
+                (count as f64 / draws as f64 - p).abs() < 0.01,
+                "{:?}",
+                counts
+            );
+        }
+    }
+
+    #[test]
+    fn zero_weights_never_drawn() {
+        let mut rng = XorShift64::new(0);
+        let weighted = WeightedIndex::new(&[0.0, 3.0, 0.0, 1e-9, 0.0, 5.0]);
+        for _ in 0..100_000 {
+            let i = weighted.sample(&mut rng);
+            assert!(i % 2 == 1, "drew zero weight index {}", i);
+        }
+        let single = WeightedIndex::new(&[0.0, 0.0, 2.5]);
+        assert!((0..1000).all(|_| single.sample(&mut rng) == 2));
+    }
+
+    #[test]
+    fn reservoir_is_uniform() {
+        let mut seeds = XorShift64::new(7);
+        let trials = 60_000;
+        // each of the 3 subsets of size 2 of [0, 1, 2]
+        let mut counts = [0; 3];
+        for _ in 0..trials {
+            let mut rng = XorShift64::new(seeds.next_u64());
+            let mut sample = rng.reservoir_sample(0..3, 2);
+            sample.sort_unstable();
+            let missing = 3 - sample[0] - sample[1];
+            counts[missing] += 1;
+        }
+        for count in counts {
+            assert!(
+                (count as f64 / trials as f64 - 1.0 / 3.0).abs() < 0.01,
+                "{:?}",
+                counts
+            );
+        }
+        let mut rng = XorShift64::new(1);
+        assert_eq!(rng.reservoir_sample(0..2, 5), vec![0, 1]);
+        assert!(rng.reservoir_sample(0..10, 0).is_empty());
+    }
+
+    #[test]
+    fn shuffle_partial_prefix() {
+        let mut rng = XorShift64::new(3);
+        let trials = 40_000;
+        let mut first = [0; 4];
+        for _ in 0..trials {
+            let mut a = [0, 1, 2, 3];
+            rng.shuffle_partial(&mut a, 2);
+            assert_ne!(a[0], a[1]);
+            let mut sorted = a;
+            sorted.sort_unstable();
+            assert_eq!(sorted, [0, 1, 2, 3]);
+            first[a[0]] += 1;
+        }
+        for count in first {
+            assert!(
+                (count as f64 / trials as f64 - 0.25).abs() < 0.01,
+                "{:?}",
+                first
+            );
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Shared handling of `RangeBounds` arguments.
+
+use std::{
+    fmt,
+    ops::{Add, Bound, RangeBounds},
+};
+
+/// Convert `range` into a half open `[l, r)` within `0..n`.
+pub(crate) fn bounds<T>(range: impl RangeBounds<T>, n: T) -> (T, T)
+where
+    T: Copy + Ord + Add<Output = T> + From<u8> + fmt::Display,
+{
+    let l = match range.start_bound() {
+        Bound::Included(&l) => l,
+        Bound::Excluded(&l) => l + T::from(1),
+        Bound::Unbounded => T::from(0),
+    };
+    let r = match range.end_bound() {
+        Bound::Included(&r) => r + T::from(1),
+        Bound::Excluded(&r) => r,
+        Bound::Unbounded => n,
+    };
+    assert!(
+        l <= r && r <= n,
+        "range {}..{} out of range for length {}",
+        l,
+        r,
+        n
+    );
+    (l, r)
+}
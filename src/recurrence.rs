@@ -0,0 +1,149 @@
+//! N-th terms of linear recurrences modulo 998244353 for huge `n`.
+//!
+//! Values are `u64` reduced modulo [`MOD998244353`], as in [`crate::convolution`].
+
+use crate::convolution::{convolution, MOD998244353};
+
+const P: u64 = MOD998244353;
+
+/// `F(n) mod 998244353` with `F(0) = 0` and `F(1) = 1`, by fast doubling in `O(log n)`.
+pub fn fib(n: u64) -> u64 {
+    // (F(k), F(k + 1)) for k = the bits of n read so far
+    let (mut a, mut b) = (0, 1);
+    for i in (0..u64::BITS - n.leading_zeros()).rev() {
+        // F(2k) = F(k) (2 F(k + 1) - F(k)), F(2k + 1) = F(k)^2 + F(k + 1)^2
+        let c = a * ((2 * b + P - a) % P) % P;
+        let d = (a * a + b * b) % P;
+        (a, b) = if n >> i & 1 == 1 {
+            (d, (c + d) % P)
+        } else {
+            (c, d)
+        };
+    }
+    a
+}
+
+/// `a_k` of `a_n = coeffs[0] a_{n-1} + ... + coeffs[d-1] a_{n-d}` starting from `a_0..a_d = init`,
+/// by Bostan-Mori in `O(d log d log k)`. The result is reduced modulo 998244353, and `coeffs` and
+/// `init` must already be, since the products are taken without reducing them first.
+pub fn linear_recurrence_kth(coeffs: &[u64], init: &[u64], k: u64) -> u64 {
+    debug_assert!(
+        coeffs.iter().chain(init).all(|&x| x < P),
+        "coeffs and init must be reduced modulo {}",
+        P
+    );
+    let d = coeffs.len();
+    assert_eq!(
+        init.len(),
+        d,
+        "one initial term per coefficient is required"
+    );
+    if k < d as u64 {
+        return init[k as usize] % P;
+    }
+    // a_k = [x^k] p(x) / q(x) with q = 1 - sum coeffs[i] x^(i + 1) and p = (init * q) mod x^d
+    let mut q = Vec::with_capacity(d + 1);
+    q.push(1);
+    q.extend(coeffs.iter().map(|&c| (P - c % P) % P));
+    let mut p = convolution(init, &q);
+    p.truncate(d);
+
+    let mut k = k;
+    while k > 0 {
+        // p(x) / q(x) = p(x) q(-x) / q(x) q(-x), whose denominator only has even powers
+        let q_neg = q
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| if i % 2 == 1 { (P - c) % P } else { c })
+            .collect::<Vec<_>>();
+        let u = convolution(&p, &q_neg);
+        let v = convolution(&q, &q_neg);
+        p = u.into_iter().skip((k % 2) as usize).step_by(2).collect();
+        q = v.into_iter().step_by(2).collect();
+        k /= 2;
+    }
+    // q(0) stays 1, so a_k is the constant term of p
+    p.first().copied().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    fn mat_mul(a: [[u64; 2]; 2], b: [[u64; 2]; 2]) -> [[u64; 2]; 2] {
+        let mut c = [[0; 2]; 2];
+        for i in 0..2 {
+            for j in 0..2 {
+                c[i][j] = (a[i][0] * b[0][j] + a[i][1] * b[1][j]) % P;
+            }
+        }
+        c
+    }
+
+    fn fib_matrix(mut n: u64) -> u64 {
+        let (mut result, mut m) = ([[1, 0], [0, 1]], [[1, 1], [1, 0]]);
+        while n > 0 {
+            if n & 1 == 1 {
+                result = mat_mul(result, m);
+            }
+            m = mat_mul(m, m);
+            n >>= 1;
+        }
+        result[0][1]
+    }
+
+    #[test]
+    fn fib_matches_matrix_power() {
+        assert_eq!(
+            (0..10).map(fib).collect::<Vec<_>>(),
+            vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+        );
+        let mut rng = XorShift64::new(62);
+        for _ in 0..100 {
+            let n = rng.next_u64();
+            assert_eq!(fib(n), fib_matrix(n));
+        }
+        assert_eq!(fib(u64::MAX), fib_matrix(u64::MAX));
+    }
+
+    #[test]
+    fn degree_3_matches_iteration() {
+        let coeffs = [3, P - 1, 7];
+        let init = [1, 4, 9];
+        let n = 100_000;
+        let mut a = init.to_vec();
+        for i in 3..n {
+            let next = (coeffs[0] * a[i - 1] + coeffs[1] * a[i - 2] + coeffs[2] * a[i - 3]) % P;
+            a.push(next);
+        }
+        let mut rng = XorShift64::new(3);
+        for _ in 0..100 {
+            let k = rng.gen_index(n);
+            assert_eq!(linear_recurrence_kth(&coeffs, &init, k as u64), a[k]);
+        }
+        assert_eq!(
+            linear_recurrence_kth(&coeffs, &init, n as u64 - 1),
+            a[n - 1]
+        );
+    }
+
+    #[test]
+    fn fibonacci_as_recurrence() {
+        let mut rng = XorShift64::new(1);
+        for _ in 0..20 {
+            let k = rng.next_u64();
+            assert_eq!(linear_recurrence_kth(&[1, 1], &[0, 1], k), fib(k));
+        }
+    }
+
+    #[test]
+    fn small_k() {
+        let coeffs = [2, 5, 1];
+        let init = [7, 8, 9];
+        assert_eq!(linear_recurrence_kth(&coeffs, &init, 0), 7);
+        assert_eq!(linear_recurrence_kth(&coeffs, &init, 2), 9);
+        assert_eq!(linear_recurrence_kth(&coeffs, &init, 3), 2 * 9 + 5 * 8 + 7);
+        assert_eq!(linear_recurrence_kth(&[], &[], 5), 0);
+    }
+}
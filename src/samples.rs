@@ -0,0 +1,64 @@
+//! Problem samples kept next to the solution they check.
+
+/// Define `PROBLEM_URL` and one test per sample case, named after its label.
+///
+/// Expects `solve(&mut Scanner<R>, &mut W)` in scope, and compares its output with the expected one
+/// ignoring trailing whitespace.
+///
+/// ```ignore
+/// atcoder_samples! {
+///     url: "https://atcoder.jp/contests/abc177/tasks/abc177_d",
+///     case1: { input: "5 3\n1 2\n3 4\n5 1\n", output: "3\n" },
+/// }
+/// ```
+#[macro_export]
+macro_rules! atcoder_samples {
+    (url: $url:expr, $($case:ident: { input: $input:expr, output: $output:expr $(,)? }),* $(,)?) => {
+        #[allow(dead_code)]
+        pub const PROBLEM_URL: &str = $url;
+
+        $(
+            #[test]
+            fn $case() {
+                let mut scanner = $crate::cio::Scanner::from($input);
+                let mut out = Vec::new();
+                solve(&mut scanner, &mut out);
+                let out = String::from_utf8(out).unwrap();
+                assert_eq!(out.trim_end(), $output.trim_end(), "{}", PROBLEM_URL);
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cio::Scanner;
+    use crate::union_find::UnionFind;
+    use std::io::{BufRead, Write};
+
+    /// ABC177 D - Friends: the size of the largest group of friends.
+    fn solve<R: BufRead, W: Write>(scanner: &mut Scanner<R>, out: &mut W) {
+        let (n, m) = scanner.tuple_2::<usize, usize>();
+        let mut uf = UnionFind::new(n);
+        for (a, b) in scanner.scan_edges(m) {
+            uf.union(a, b);
+        }
+        let answer = (0..n).map(|x| uf.size(x)).max().unwrap();
+        writeln!(out, "{}", answer).unwrap();
+    }
+
+    atcoder_samples! {
+        url: "https://atcoder.jp/contests/abc177/tasks/abc177_d",
+        case1: { input: "5 3\n1 2\n3 4\n5 1\n", output: "3\n" },
+        case2: {
+            input: "4 10\n1 2\n2 1\n1 2\n2 1\n1 2\n1 3\n1 4\n2 3\n2 4\n3 4\n",
+            output: "4\n",
+        },
+        case3: { input: "10 4\n3 1\n4 1\n5 9\n2 6\n", output: "3\n" },
+    }
+
+    #[test]
+    fn url_is_registered() {
+        assert!(PROBLEM_URL.ends_with("abc177_d"));
+    }
+}
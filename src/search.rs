@@ -0,0 +1,146 @@
+//! Binary and ternary search over real numbers.
+//!
+//! Loops like `while hi - lo > 1e-9` never terminate when `lo` and `hi` are so large that
+//! adjacent floats are further apart than the epsilon, so these search a fixed number of steps instead.
+
+/// Smallest `x` in `[lo, hi]` with `pred(x)`, assuming `pred` is false then true.
+/// Each iteration halves the interval, so `iters` bounds the precision to `(hi - lo) / 2^iters`; use
+/// `bisect_f64_ulp` to converge to adjacent floats.
+pub fn bisect_f64(mut lo: f64, mut hi: f64, iters: u32, mut pred: impl FnMut(f64) -> bool) -> f64 {
+    debug_assert!(lo <= hi, "lo {} is greater than hi {}", lo, hi);
+    debug_assert!(!pred(lo), "pred is already true at lo {}", lo);
+    debug_assert!(pred(hi), "pred is false at hi {}", hi);
+    for _ in 0..iters {
+        let mid = lo + (hi - lo) / 2.0;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
+/// Smallest `x` in `[lo, hi]` with `pred(x)`, exact to the last bit.
+/// Bisects on the ordered bit representation, so it takes at most 64 steps.
+pub fn bisect_f64_ulp(lo: f64, hi: f64, mut pred: impl FnMut(f64) -> bool) -> f64 {
+    debug_assert!(lo <= hi, "lo {} is greater than hi {}", lo, hi);
+    debug_assert!(!pred(lo), "pred is already true at lo {}", lo);
+    debug_assert!(pred(hi), "pred is false at hi {}", hi);
+    let (mut lo, mut hi) = (to_ordered(lo), to_ordered(hi));
+    while hi as i128 - lo as i128 > 1 {
+        let mid = ((lo as i128 + hi as i128) / 2) as i64;
+        if pred(from_ordered(mid)) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    from_ordered(hi)
+}
+
+/// Minimizer of `f` on `[lo, hi]`, assuming `f` is strictly decreasing then increasing.
+/// Shrinks the interval by a third `iters` times; 200 iterations are enough for any finite interval.
+pub fn ternary_f64(mut lo: f64, mut hi: f64, iters: u32, mut f: impl FnMut(f64) -> f64) -> f64 {
+    debug_assert!(lo <= hi, "lo {} is greater than hi {}", lo, hi);
+    for _ in 0..iters {
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if f(m1) < f(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    lo + (hi - lo) / 2.0
+}
+
+/// Map a float to an integer preserving order, so adjacent floats map to adjacent integers.
+fn to_ordered(x: f64) -> i64 {
+    debug_assert!(!x.is_nan());
+    let bits = x.to_bits() as i64;
+    if bits >= 0 {
+        bits
+    } else {
+        i64::MIN - bits
+    }
+}
+
+fn from_ordered(key: i64) -> f64 {
+    if key >= 0 {
+        f64::from_bits(key as u64)
+    } else {
+        f64::from_bits((i64::MIN - key) as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sqrt2() {
+        let pred = |x: f64| x * x >= 2.0;
+        let x = bisect_f64_ulp(0.0, 2.0, pred);
+        assert!(pred(x));
+        assert!(!pred(from_ordered(to_ordered(x) - 1)));
+        assert!((x - 2f64.sqrt()).abs() <= f64::EPSILON);
+
+        assert_eq!(bisect_f64(0.0, 2.0, 100, pred), x);
+    }
+
+    #[test]
+    fn huge_magnitude() {
+        let x = bisect_f64_ulp(0.0, 1e308, |x| x >= 1e300);
+        assert_eq!(x, 1e300);
+        let x = bisect_f64_ulp(-1e308, 1e308, |x| x >= -1e300);
+        assert_eq!(x, -1e300);
+
+        let x = bisect_f64(0.0, 1e308, 200, |x| x >= 1e300);
+        assert!((x - 1e300).abs() / 1e300 <= f64::EPSILON);
+    }
+
+    #[test]
+    fn ordered_keys() {
+        let xs = [
+            -1e300,
+            -1.0,
+            -f64::MIN_POSITIVE,
+            0.0,
+            1e-300,
+            1.0,
+            2.5,
+            1e300,
+        ];
+        for w in xs.windows(2) {
+            assert!(to_ordered(w[0]) < to_ordered(w[1]));
+        }
+        for x in xs {
+            assert_eq!(from_ordered(to_ordered(x)), x);
+        }
+        assert_eq!(to_ordered(1.0) + 1, to_ordered(1.0 + f64::EPSILON));
+    }
+
+    #[test]
+    fn ternary_minimum() {
+        let x = ternary_f64(-10.0, 10.0, 200, |x| (x - 1.5) * (x - 1.5) + 3.0);
+        assert!((x - 1.5).abs() < 1e-7);
+        let x = ternary_f64(0.0, 1e300, 200, |x| (x - 1e299).abs());
+        assert!((x - 1e299).abs() / 1e299 < 1e-12);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "pred is already true")]
+    fn non_monotone_predicate() {
+        // true at both ends, so there is no boundary to find
+        bisect_f64(0.0, 2.0, 100, |x| !(0.5..=1.5).contains(&x));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "pred is false at hi")]
+    fn boundary_outside_interval() {
+        bisect_f64_ulp(0.0, 2.0, |x| x * x >= 5.0);
+    }
+}
@@ -0,0 +1,284 @@
+//! Segment tree over a monoid given as an identity element and an associative operation.
+
+use std::ops::RangeBounds;
+
+use crate::range::bounds;
+
+pub struct SegmentTree<T, F> {
+    n: usize,
+    size: usize,
+    data: Vec<T>,
+    identity: T,
+    op: F,
+}
+
+impl<T, F> SegmentTree<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    pub fn new(n: usize, identity: T, op: F) -> Self {
+        Self::from_vec(vec![identity.clone(); n], identity, op)
+    }
+
+    /// Build from leaves computed by `leaf(i)` for `i` in `0..n`.
+    pub fn from_fn(n: usize, leaf: impl FnMut(usize) -> T, identity: T, op: F) -> Self {
+        Self::from_vec((0..n).map(leaf).collect(), identity, op)
+    }
+
+    pub fn from_vec(values: Vec<T>, identity: T, op: F) -> Self {
+        let mut tree = Self::with_leaves(values, identity, op);
+        for i in (1..tree.size).rev() {
+            tree.data[i] = (tree.op)(&tree.data[2 * i], &tree.data[2 * i + 1]);
+        }
+        tree
+    }
+
+    /// Allocate the buffer and place the leaves, leaving internal nodes as identity.
+    fn with_leaves(values: Vec<T>, identity: T, op: F) -> Self {
+        let n = values.len();
+        let size = n.next_power_of_two();
+        let mut data = vec![identity.clone(); 2 * size];
+        for (i, v) in values.into_iter().enumerate() {
+            data[size + i] = v;
+        }
+        Self {
+            n,
+            size,
+            data,
+            identity,
+            op,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn get(&self, i: usize) -> &T {
+        assert!(i < self.n, "index {} out of range for length {}", i, self.n);
+        &self.data[self.size + i]
+    }
+
+    pub fn update(&mut self, i: usize, value: T) {
+        assert!(i < self.n, "index {} out of range for length {}", i, self.n);
+        let mut i = i + self.size;
+        self.data[i] = value;
+        while i > 1 {
+            i /= 2;
+            self.data[i] = (self.op)(&self.data[2 * i], &self.data[2 * i + 1]);
+        }
+    }
+
+    /// Fold of the values in `range`.
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = bounds(range, self.n);
+        let (mut l, mut r) = (l + self.size, r + self.size);
+        let mut left = self.identity.clone();
+        let mut right = self.identity.clone();
+        while l < r {
+            if l & 1 == 1 {
+                left = (self.op)(&left, &self.data[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right = (self.op)(&self.data[r], &right);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        (self.op)(&left, &right)
+    }
+
+    /// Fold of `[0, end)`.
+    pub fn query_prefix(&self, end: usize) -> T {
+        self.query(..end)
+    }
+
+    /// Fold of `[start, n)`.
+    pub fn query_suffix(&self, start: usize) -> T {
+        self.query(start..)
+    }
+}
+
+impl SegmentTree<u64, fn(&u64, &u64) -> u64> {
+    /// Range gcd with `0` as the identity, since `gcd(0, x) = x`.
+    pub fn gcd(values: Vec<u64>) -> Self {
+        fn gcd(a: &u64, b: &u64) -> u64 {
+            let (mut a, mut b) = (*a, *b);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        }
+        Self::from_vec(values, 0, gcd)
+    }
+}
+
+/// Summary of a segment for maximum subarray sums, where subarrays are non-empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxSubarray {
+    pub total: i64,
+    /// Best sum of a subarray anywhere in the segment.
+    pub best: i64,
+    /// Best sum of a subarray starting at the left end.
+    pub prefix: i64,
+    /// Best sum of a subarray ending at the right end.
+    pub suffix: i64,
+}
+
+impl MaxSubarray {
+    /// Stands in for `-inf` in the empty segment, small enough to lose every `max` and far enough from
+    /// `i64::MIN` that adding two of them does not overflow.
+    const NEG_INF: i64 = i64::MIN / 4;
+
+    const EMPTY: Self = Self {
+        total: 0,
+        best: Self::NEG_INF,
+        prefix: Self::NEG_INF,
+        suffix: Self::NEG_INF,
+    };
+
+    /// Segment of the single value `v`.
+    pub fn new(v: i64) -> Self {
+        Self {
+            total: v,
+            best: v,
+            prefix: v,
+            suffix: v,
+        }
+    }
+
+    fn merge(a: &Self, b: &Self) -> Self {
+        Self {
+            total: a.total + b.total,
+            best: a.best.max(b.best).max(a.suffix + b.prefix),
+            prefix: a.prefix.max(a.total + b.prefix),
+            suffix: b.suffix.max(b.total + a.suffix),
+        }
+    }
+}
+
+impl SegmentTree<MaxSubarray, fn(&MaxSubarray, &MaxSubarray) -> MaxSubarray> {
+    /// Maximum subarray sum in a range as `query(range).best`, with `update(i, MaxSubarray::new(v))`.
+    pub fn max_subarray(values: &[i64]) -> Self {
+        let leaves = values.iter().map(|&v| MaxSubarray::new(v)).collect();
+        Self::from_vec(leaves, MaxSubarray::EMPTY, MaxSubarray::merge)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, F> SegmentTree<T, F>
+where
+    T: Clone + Send + Sync,
+    F: Fn(&T, &T) -> T + Sync,
+{
+    /// Same tree as `from_vec`, computing the nodes of each level in parallel.
+    pub fn from_vec_par(values: Vec<T>, identity: T, op: F) -> Self {
+        use rayon::prelude::*;
+
+        let mut tree = Self::with_leaves(values, identity, op);
+        let op = &tree.op;
+        let mut width = tree.size / 2;
+        while width >= 1 {
+            // nodes of this level are width..2 * width, their children are 2 * width..4 * width
+            let (upper, lower) = tree.data.split_at_mut(2 * width);
+            upper[width..]
+                .par_iter_mut()
+                .enumerate()
+                .for_each(|(i, node)| *node = op(&lower[2 * i], &lower[2 * i + 1]));
+            width /= 2;
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    #[test]
+    fn query_and_update() {
+        let mut tree = SegmentTree::from_vec(vec![5, 3, 8, 1, 9], i64::MAX, |a, b| *a.min(b));
+        assert_eq!(tree.query(..), 1);
+        assert_eq!(tree.query(0..3), 3);
+        assert_eq!(tree.query(4..=4), 9);
+        assert_eq!(tree.query(2..2), i64::MAX);
+        tree.update(3, 10);
+        assert_eq!(tree.query(..), 3);
+        assert_eq!(*tree.get(3), 10);
+    }
+
+    #[test]
+    fn random_sum() {
+        let mut rng = XorShift64::new(3);
+        let n = 37;
+        let mut a = (0..n).map(|_| rng.gen_range(0..100)).collect::<Vec<_>>();
+        let mut tree = SegmentTree::from_fn(n, |i| a[i], 0, |x, y| x + y);
+        for _ in 0..500 {
+            let i = rng.gen_index(n);
+            a[i] = rng.gen_range(0..100);
+            tree.update(i, a[i]);
+            let l = rng.gen_index(n + 1);
+            let r = l + rng.gen_index(n + 1 - l);
+            assert_eq!(tree.query(l..r), a[l..r].iter().sum::<u64>());
+        }
+    }
+
+    #[test]
+    fn range_gcd() {
+        let mut tree = SegmentTree::gcd(vec![12, 18, 6]);
+        assert_eq!(tree.query(0..2), 6);
+        assert_eq!(tree.query(..), 6);
+        tree.update(0, 9);
+        assert_eq!(tree.query(0..2), 9);
+        assert_eq!(tree.query(1..), 6);
+        assert_eq!(tree.query(1..1), 0);
+    }
+
+    #[test]
+    fn max_subarray() {
+        let mut tree = SegmentTree::max_subarray(&[-1, 2, 3, -2, 4]);
+        assert_eq!(tree.query(..).best, 7);
+        assert_eq!(tree.query(0..4).best, 5);
+        assert_eq!(tree.query(3..4).best, -2);
+        tree.update(3, MaxSubarray::new(-10));
+        assert_eq!(tree.query(..).best, 5);
+        assert_eq!(tree.query(3..).best, 4);
+    }
+
+    #[test]
+    fn prefix_and_suffix() {
+        let a = vec!["a", "b", "c", "d"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let tree = SegmentTree::from_vec(a, String::new(), |x, y| format!("{}{}", x, y));
+        for i in 0..=tree.len() {
+            assert_eq!(tree.query_prefix(i), tree.query(0..i));
+            assert_eq!(tree.query_suffix(i), tree.query(i..tree.len()));
+        }
+        assert_eq!(tree.query_prefix(3), "abc");
+        assert_eq!(tree.query_suffix(1), "bcd");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_build_matches_sequential() {
+        let mut rng = XorShift64::new(5);
+        for n in [0, 1, 2, 3, 100, 1 << 12, 100_000] {
+            let a = (0..n)
+                .map(|_| rng.gen_range(0..1 << 30))
+                .collect::<Vec<_>>();
+            let seq = SegmentTree::from_vec(a.clone(), 0, |x, y| x ^ y);
+            let par = SegmentTree::from_vec_par(a, 0, |x, y| x ^ y);
+            assert_eq!(seq.data, par.data);
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Sequence with insertion and erasure at any position, range folds and range reversal, as an
+//! implicit treap keyed by position.
+
+use std::ops::RangeBounds;
+
+use crate::{random::XorShift64, range::bounds};
+
+const NONE: usize = usize::MAX;
+
+struct Node<T> {
+    value: T,
+    /// Fold of the subtree in order, and in reverse order for when it gets reversed.
+    sum: T,
+    rev_sum: T,
+    len: usize,
+    priority: u64,
+    children: [usize; 2],
+    /// The children are still to be swapped and reversed. `sum` and `rev_sum` are already swapped.
+    reversed: bool,
+}
+
+pub struct Sequence<T, F> {
+    nodes: Vec<Node<T>>,
+    /// Slots of erased nodes, reused by `insert`.
+    free: Vec<usize>,
+    root: usize,
+    identity: T,
+    op: F,
+    rng: XorShift64,
+}
+
+impl<T, F> Sequence<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    /// Empty sequence folded with the monoid `(identity, op)`. `op` need not be commutative.
+    pub fn new(identity: T, op: F) -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NONE,
+            identity,
+            op,
+            rng: XorShift64::new(0x5eed),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root == NONE
+    }
+
+    /// Insert `value` so that it ends up at index `pos`.
+    pub fn insert(&mut self, pos: usize, value: T) {
+        assert!(
+            pos <= self.len(),
+            "position {} out of range for length {}",
+            pos,
+            self.len()
+        );
+        let node = Node {
+            sum: value.clone(),
+            rev_sum: value.clone(),
+            value,
+            len: 1,
+            priority: self.rng.next_u64(),
+            children: [NONE; 2],
+            reversed: false,
+        };
+        let v = match self.free.pop() {
+            Some(v) => {
+                self.nodes[v] = node;
+                v
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        let (l, r) = self.split(self.root, pos);
+        let l = self.merge(l, v);
+        self.root = self.merge(l, r);
+    }
+
+    /// Remove and return the value at `pos`.
+    pub fn erase(&mut self, pos: usize) -> T {
+        assert!(
+            pos < self.len(),
+            "position {} out of range for length {}",
+            pos,
+            self.len()
+        );
+        let (l, rest) = self.split(self.root, pos);
+        let (v, r) = self.split(rest, 1);
+        self.root = self.merge(l, r);
+        self.free.push(v);
+        self.nodes[v].value.clone()
+    }
+
+    pub fn get(&mut self, pos: usize) -> T {
+        self.range_query(pos..=pos)
+    }
+
+    /// Fold of the values in `range`, in order.
+    pub fn range_query(&mut self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = bounds(range, self.len());
+        let (left, mid, right) = self.split3(l, r);
+        let sum = if mid == NONE {
+            self.identity.clone()
+        } else {
+            self.nodes[mid].sum.clone()
+        };
+        self.merge3(left, mid, right);
+        sum
+    }
+
+    /// Reverse the order of the values in `range`.
+    pub fn reverse(&mut self, range: impl RangeBounds<usize>) {
+        let (l, r) = bounds(range, self.len());
+        let (left, mid, right) = self.split3(l, r);
+        self.toggle(mid);
+        self.merge3(left, mid, right);
+    }
+
+    /// Values in order.
+    pub fn to_vec(&mut self) -> Vec<T> {
+        (0..self.len()).map(|i| self.get(i)).collect()
+    }
+
+    fn size(&self, v: usize) -> usize {
+        if v == NONE {
+            0
+        } else {
+            self.nodes[v].len
+        }
+    }
+
+    fn toggle(&mut self, v: usize) {
+        if v != NONE {
+            let node = &mut self.nodes[v];
+            node.reversed ^= true;
+            std::mem::swap(&mut node.sum, &mut node.rev_sum);
+        }
+    }
+
+    fn push(&mut self, v: usize) {
+        if self.nodes[v].reversed {
+            self.nodes[v].reversed = false;
+            self.nodes[v].children.swap(0, 1);
+            let [l, r] = self.nodes[v].children;
+            self.toggle(l);
+            self.toggle(r);
+        }
+    }
+
+    fn pull(&mut self, v: usize) {
+        let [l, r] = self.nodes[v].children;
+        let fold = |side: usize, sum: fn(&Node<T>) -> &T| {
+            if side == NONE {
+                self.identity.clone()
+            } else {
+                sum(&self.nodes[side]).clone()
+            }
+        };
+        let (l_sum, r_sum) = (fold(l, |n| &n.sum), fold(r, |n| &n.sum));
+        let (l_rev, r_rev) = (fold(l, |n| &n.rev_sum), fold(r, |n| &n.rev_sum));
+        let value = &self.nodes[v].value;
+        let sum = (self.op)(&(self.op)(&l_sum, value), &r_sum);
+        let rev_sum = (self.op)(&(self.op)(&r_rev, value), &l_rev);
+        let len = 1 + self.size(l) + self.size(r);
+        let node = &mut self.nodes[v];
+        (node.sum, node.rev_sum, node.len) = (sum, rev_sum, len);
+    }
+
+    /// Split the subtree `v` into its first `k` values and the rest.
+    fn split(&mut self, v: usize, k: usize) -> (usize, usize) {
+        if v == NONE {
+            return (NONE, NONE);
+        }
+        self.push(v);
+        let [l, r] = self.nodes[v].children;
+        if k <= self.size(l) {
+            let (a, b) = self.split(l, k);
+            self.nodes[v].children[0] = b;
+            self.pull(v);
+            (a, v)
+        } else {
+            let (a, b) = self.split(r, k - self.size(l) - 1);
+            self.nodes[v].children[1] = a;
+            self.pull(v);
+            (v, b)
+        }
+    }
+
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NONE {
+            return b;
+        }
+        if b == NONE {
+            return a;
+        }
+        if self.nodes[a].priority > self.nodes[b].priority {
+            self.push(a);
+            self.nodes[a].children[1] = self.merge(self.nodes[a].children[1], b);
+            self.pull(a);
+            a
+        } else {
+            self.push(b);
+            self.nodes[b].children[0] = self.merge(a, self.nodes[b].children[0]);
+            self.pull(b);
+            b
+        }
+    }
+
+    /// Split the whole sequence into `[0, l)`, `[l, r)` and `[r, len)`.
+    fn split3(&mut self, l: usize, r: usize) -> (usize, usize, usize) {
+        let (left, rest) = self.split(self.root, l);
+        let (mid, right) = self.split(rest, r - l);
+        (left, mid, right)
+    }
+
+    fn merge3(&mut self, left: usize, mid: usize, right: usize) {
+        let left = self.merge(left, mid);
+        self.root = self.merge(left, right);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn insert_reverse_query() {
+        let mut seq = Sequence::new(0i64, |a, b| a + b);
+        for (i, v) in [1, 2, 3, 4, 5].into_iter().enumerate() {
+            seq.insert(i, v);
+        }
+        seq.insert(0, 10);
+        assert_eq!(seq.to_vec(), vec![10, 1, 2, 3, 4, 5]);
+        seq.reverse(1..5);
+        assert_eq!(seq.to_vec(), vec![10, 4, 3, 2, 1, 5]);
+        assert_eq!(seq.range_query(..), 25);
+        assert_eq!(seq.range_query(1..3), 7);
+        assert_eq!(seq.range_query(2..2), 0);
+        assert_eq!(seq.erase(1), 4);
+        assert_eq!(seq.to_vec(), vec![10, 3, 2, 1, 5]);
+        assert_eq!(seq.len(), 5);
+    }
+
+    #[test]
+    fn matches_vec_with_non_commutative_fold() {
+        let mut rng = XorShift64::new(80);
+        let mut seq = Sequence::new(String::new(), |a: &String, b: &String| {
+            format!("{}{}", a, b)
+        });
+        let mut a: Vec<String> = Vec::new();
+        for step in 0..2000 {
+            match rng.gen_index(4) {
+                0 | 1 => {
+                    let pos = rng.gen_index(a.len() + 1);
+                    let v = ((b'a' + (step % 26) as u8) as char).to_string();
+                    a.insert(pos, v.clone());
+                    seq.insert(pos, v);
+                }
+                2 if !a.is_empty() => {
+                    let pos = rng.gen_index(a.len());
+                    assert_eq!(seq.erase(pos), a.remove(pos));
+                }
+                _ => {
+                    let l = rng.gen_index(a.len() + 1);
+                    let r = l + rng.gen_index(a.len() + 1 - l);
+                    a[l..r].reverse();
+                    seq.reverse(l..r);
+                }
+            }
+            let l = rng.gen_index(a.len() + 1);
+            let r = l + rng.gen_index(a.len() + 1 - l);
+            assert_eq!(seq.range_query(l..r), a[l..r].concat());
+        }
+        assert_eq!(seq.to_vec(), a);
+        assert!(seq.nodes.len() <= 2000);
+    }
+}
@@ -0,0 +1,183 @@
+//! Minimum spanning trees, and counting spanning trees with the matrix-tree theorem.
+//!
+//! The count equals any cofactor of the Laplacian, here the determinant with the last row and column removed.
+//! Parallel edges are counted separately and self loops are ignored.
+
+use crate::union_find::UnionFind;
+
+/// Minimum spanning forest of `(weight, u, v)` edges, such as those read by
+/// `Scanner::scan_weighted_edge_list`, as its total weight and edges in increasing weight.
+pub fn kruskal(n: usize, edges: &[(u64, usize, usize)]) -> (u64, Vec<(u64, usize, usize)>) {
+    let mut edges = edges.to_vec();
+    edges.sort_unstable();
+    let mut uf = UnionFind::new(n);
+    let tree = edges
+        .into_iter()
+        .filter(|&(_, u, v)| uf.union(u, v))
+        .collect::<Vec<_>>();
+    (tree.iter().map(|&(w, _, _)| w).sum(), tree)
+}
+
+/// Laplacian of the multigraph without the last row and column.
+fn laplacian_minor(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<i64>> {
+    let mut l = vec![vec![0; n]; n];
+    for &(u, v) in edges {
+        if u != v {
+            l[u][u] += 1;
+            l[v][v] += 1;
+            l[u][v] -= 1;
+            l[v][u] -= 1;
+        }
+    }
+    l.truncate(n.saturating_sub(1));
+    l.iter_mut().for_each(|row| row.truncate(n - 1));
+    l
+}
+
+/// Number of spanning trees modulo the prime `modulus`, by Gaussian elimination in `O(n^3)`.
+pub fn count_spanning_trees_mod(n: usize, edges: &[(usize, usize)], modulus: u64) -> u64 {
+    assert!(n > 0, "the empty graph has no vertices");
+    let m = modulus as u128;
+    let mut a = laplacian_minor(n, edges)
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|x| x.rem_euclid(modulus as i64) as u128)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let pow = |mut x: u128, mut e: u128| {
+        let mut result = 1;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * x % m;
+            }
+            x = x * x % m;
+            e >>= 1;
+        }
+        result
+    };
+
+    let mut det = 1 % m;
+    for k in 0..a.len() {
+        let Some(pivot) = (k..a.len()).find(|&i| a[i][k] != 0) else {
+            return 0;
+        };
+        if pivot != k {
+            a.swap(pivot, k);
+            det = (m - det) % m;
+        }
+        det = det * a[k][k] % m;
+        let inv = pow(a[k][k], m - 2);
+        let (upper, lower) = a.split_at_mut(k + 1);
+        let pivot_row = &upper[k];
+        for row in lower {
+            let factor = row[k] * inv % m;
+            for (x, &p) in row.iter_mut().zip(pivot_row).skip(k) {
+                *x = (*x + m - factor * p % m) % m;
+            }
+        }
+    }
+    det as u64
+}
+
+/// Exact number of spanning trees by fraction-free (Bareiss) elimination, for graphs whose
+/// intermediate values fit in `i128`.
+pub fn count_spanning_trees(n: usize, edges: &[(usize, usize)]) -> i128 {
+    assert!(n > 0, "the empty graph has no vertices");
+    let mut a = laplacian_minor(n, edges)
+        .into_iter()
+        .map(|row| row.into_iter().map(i128::from).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let size = a.len();
+    let (mut sign, mut prev) = (1, 1);
+    for k in 0..size {
+        let Some(pivot) = (k..size).find(|&i| a[i][k] != 0) else {
+            return 0;
+        };
+        if pivot != k {
+            a.swap(pivot, k);
+            sign = -sign;
+        }
+        for i in k + 1..size {
+            for j in k + 1..size {
+                // exact division is guaranteed by Sylvester's identity
+                a[i][j] = (a[i][j] * a[k][k] - a[i][k] * a[k][j]) / prev;
+            }
+        }
+        prev = a[k][k];
+    }
+    if size == 0 {
+        1
+    } else {
+        sign * a[size - 1][size - 1]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    const MOD: u64 = 998_244_353;
+
+    #[test]
+    fn minimum_spanning_forest() {
+        let edges = [(4, 0, 1), (1, 1, 2), (3, 0, 2), (2, 3, 4), (5, 2, 2)];
+        let (total, tree) = kruskal(5, &edges);
+        assert_eq!(total, 6);
+        assert_eq!(tree, vec![(1, 1, 2), (2, 3, 4), (3, 0, 2)]);
+    }
+
+    fn check(n: usize, edges: &[(usize, usize)], expected: i128) {
+        assert_eq!(count_spanning_trees(n, edges), expected);
+        assert_eq!(
+            count_spanning_trees_mod(n, edges, MOD),
+            expected.rem_euclid(MOD as i128) as u64
+        );
+    }
+
+    #[test]
+    fn trees_and_cycles() {
+        check(1, &[], 1);
+        check(5, &[(0, 1), (1, 2), (1, 3), (3, 4)], 1);
+        check(4, &[(0, 1), (2, 3)], 0);
+        for n in 3..10 {
+            let cycle = (0..n).map(|v| (v, (v + 1) % n)).collect::<Vec<_>>();
+            check(n, &cycle, n as i128);
+        }
+    }
+
+    #[test]
+    fn cayley() {
+        for n in 1..=12 {
+            let complete = (0..n)
+                .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+                .collect::<Vec<_>>();
+            check(n, &complete, (n as i128).pow(n.saturating_sub(2) as u32));
+        }
+    }
+
+    #[test]
+    fn multigraph() {
+        check(2, &[(0, 1), (1, 0), (0, 1)], 3);
+        // doubled edge 0-1 in a triangle: 2 + 2 + 1 trees
+        check(3, &[(0, 1), (0, 1), (1, 2), (2, 0), (2, 2)], 5);
+    }
+
+    #[test]
+    fn mod_matches_exact() {
+        let mut rng = XorShift64::new(29);
+        for _ in 0..50 {
+            let n = 1 + rng.gen_index(10);
+            let edges = (0..rng.gen_index(4 * n))
+                .map(|_| (rng.gen_index(n), rng.gen_index(n)))
+                .collect::<Vec<_>>();
+            let exact = count_spanning_trees(n, &edges);
+            assert_eq!(
+                count_spanning_trees_mod(n, &edges, MOD),
+                exact.rem_euclid(MOD as i128) as u64
+            );
+        }
+    }
+}
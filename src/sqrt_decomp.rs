@@ -0,0 +1,119 @@
+//! Square root decomposition: point update and range fold in `O(sqrt(n))`.
+//!
+//! Same interface as `SegmentTree`, with only one level of aggregates to reason about.
+
+use std::ops::RangeBounds;
+
+use crate::range::bounds;
+
+pub struct SqrtDecomp<T, F> {
+    values: Vec<T>,
+    block: usize,
+    /// Fold of each block of `values`.
+    blocks: Vec<T>,
+    identity: T,
+    op: F,
+}
+
+impl<T, F> SqrtDecomp<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    pub fn new(n: usize, identity: T, op: F) -> Self {
+        Self::from_vec(vec![identity.clone(); n], identity, op)
+    }
+
+    pub fn from_vec(values: Vec<T>, identity: T, op: F) -> Self {
+        let block = ((values.len() as f64).sqrt() as usize).max(1);
+        let blocks = values
+            .chunks(block)
+            .map(|chunk| chunk.iter().fold(identity.clone(), |acc, v| op(&acc, v)))
+            .collect();
+        Self {
+            values,
+            block,
+            blocks,
+            identity,
+            op,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> &T {
+        &self.values[i]
+    }
+
+    pub fn update(&mut self, i: usize, value: T) {
+        let n = self.len();
+        assert!(i < n, "index {} out of range for length {}", i, n);
+        self.values[i] = value;
+        let b = i / self.block;
+        let chunk = &self.values[b * self.block..n.min((b + 1) * self.block)];
+        self.blocks[b] = self.fold(chunk);
+    }
+
+    /// Fold of the values in `range`.
+    pub fn query(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = bounds(range, self.len());
+        let (bl, br) = (l / self.block, r / self.block);
+        if bl == br {
+            return self.fold(&self.values[l..r]);
+        }
+        // partial head, whole blocks in between, partial tail
+        let head = self.fold(&self.values[l..(bl + 1) * self.block]);
+        let body = self.fold(&self.blocks[bl + 1..br]);
+        let tail = self.fold(&self.values[br * self.block..r]);
+        (self.op)(&(self.op)(&head, &body), &tail)
+    }
+
+    fn fold(&self, values: &[T]) -> T {
+        values
+            .iter()
+            .fold(self.identity.clone(), |acc, v| (self.op)(&acc, v))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+    use crate::segtree::SegmentTree;
+
+    #[test]
+    fn matches_segment_tree() {
+        let mut rng = XorShift64::new(20);
+        for n in [0, 1, 2, 10, 37, 100] {
+            let a = (0..n).map(|_| rng.gen_range(0..100)).collect::<Vec<_>>();
+            let mut sqrt = SqrtDecomp::from_vec(a.clone(), 0, |x, y| x + y);
+            let mut tree = SegmentTree::from_vec(a, 0, |x, y| x + y);
+            for _ in 0..500 {
+                if n > 0 {
+                    let i = rng.gen_index(n);
+                    let v = rng.gen_range(0..100);
+                    sqrt.update(i, v);
+                    tree.update(i, v);
+                }
+                let l = rng.gen_index(n + 1);
+                let r = l + rng.gen_index(n + 1 - l);
+                assert_eq!(sqrt.query(l..r), tree.query(l..r));
+            }
+        }
+    }
+
+    #[test]
+    fn non_commutative_op() {
+        let a = "abcdefghij".chars().map(String::from).collect::<Vec<_>>();
+        let sqrt = SqrtDecomp::from_vec(a, String::new(), |x, y| format!("{}{}", x, y));
+        assert_eq!(sqrt.query(..), "abcdefghij");
+        assert_eq!(sqrt.query(2..9), "cdefghi");
+        assert_eq!(sqrt.query(4..=4), "e");
+    }
+}
@@ -0,0 +1,344 @@
+//! Random inputs for stress testing a solution against a brute force.
+//!
+//! Each generator returns the input as the judge would print it, together with the structured value.
+//! Inputs on which the solutions disagree are saved under `stress_corpus/<solver_id>/` and replayed
+//! as regression cases.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    fmt::Write,
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use crate::random::XorShift64;
+
+fn join<T: ToString>(values: impl IntoIterator<Item = T>) -> String {
+    values
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `n` values drawn from `range`, printed as `n` and then the values on one line.
+pub fn gen_array(rng: &mut XorShift64, n: usize, range: Range<i64>) -> (String, Vec<i64>) {
+    assert!(range.start < range.end, "empty range");
+    let width = range.end.abs_diff(range.start);
+    let a = (0..n)
+        .map(|_| range.start.wrapping_add(rng.gen_range(0..width) as i64))
+        .collect::<Vec<_>>();
+    (format!("{}\n{}\n", n, join(&a)), a)
+}
+
+/// Uniformly labelled random tree on `n` vertices, printed as `n` and one edge per line.
+///
+/// `one_indexed` only affects the text; the returned edges are 0-indexed.
+pub fn gen_tree_edges(
+    rng: &mut XorShift64,
+    n: usize,
+    one_indexed: bool,
+) -> (String, Vec<(usize, usize)>) {
+    let mut label = (0..n).collect::<Vec<_>>();
+    rng.shuffle(&mut label);
+    let mut edges = (1..n)
+        .map(|v| (label[rng.gen_index(v)], label[v]))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut edges);
+    let mut text = format!("{}\n", n);
+    let offset = usize::from(one_indexed);
+    for &(u, v) in &edges {
+        writeln!(text, "{} {}", u + offset, v + offset).unwrap();
+    }
+    (text, edges)
+}
+
+/// Connected simple graph with `n` vertices and `m` edges, without self loops or parallel edges,
+/// printed as `n m` and one 1-indexed edge per line.
+pub fn gen_connected_graph(
+    rng: &mut XorShift64,
+    n: usize,
+    m: usize,
+) -> (String, Vec<(usize, usize)>) {
+    assert!(n >= 1, "a graph needs a vertex");
+    assert!(
+        n - 1 <= m && m <= n * (n - 1) / 2,
+        "{} edges cannot make a connected simple graph on {} vertices",
+        m,
+        n
+    );
+    let (_, mut edges) = gen_tree_edges(rng, n, false);
+    let mut used = edges
+        .iter()
+        .map(|&(u, v)| (u.min(v), u.max(v)))
+        .collect::<HashSet<_>>();
+    while edges.len() < m {
+        let (u, v) = (rng.gen_index(n), rng.gen_index(n));
+        if u != v && used.insert((u.min(v), u.max(v))) {
+            edges.push((u, v));
+        }
+    }
+    rng.shuffle(&mut edges);
+    let mut text = format!("{} {}\n", n, m);
+    for &(u, v) in &edges {
+        writeln!(text, "{} {}", u + 1, v + 1).unwrap();
+    }
+    (text, edges)
+}
+
+/// `h` x `w` grid of characters from `charset`, printed as `h w` and one row per line.
+pub fn gen_grid(
+    rng: &mut XorShift64,
+    h: usize,
+    w: usize,
+    charset: &[char],
+) -> (String, Vec<Vec<char>>) {
+    assert!(!charset.is_empty(), "empty charset");
+    let grid = (0..h)
+        .map(|_| {
+            (0..w)
+                .map(|_| charset[rng.gen_index(charset.len())])
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut text = format!("{} {}\n", h, w);
+    for row in &grid {
+        writeln!(text, "{}", row.iter().collect::<String>()).unwrap();
+    }
+    (text, grid)
+}
+
+/// String of length `n` over `alphabet`, printed on its own line.
+pub fn gen_string(rng: &mut XorShift64, n: usize, alphabet: &[char]) -> (String, String) {
+    assert!(!alphabet.is_empty(), "empty alphabet");
+    let s = (0..n)
+        .map(|_| alphabet[rng.gen_index(alphabet.len())])
+        .collect::<String>();
+    (format!("{}\n", s), s)
+}
+
+/// Directory of saved failing inputs, relative to the crate root where `cargo test` runs.
+pub const CORPUS_DIR: &str = "stress_corpus";
+
+/// Compare `fast` with `brute` on `iterations` generated inputs, saving the first input where they
+/// disagree to the corpus and returning it.
+pub fn find_counterexample(
+    solver_id: &str,
+    seed: u64,
+    iterations: usize,
+    gen: impl FnMut(&mut XorShift64) -> String,
+    fast: impl Fn(&str) -> String,
+    brute: impl Fn(&str) -> String,
+) -> io::Result<Option<String>> {
+    find_counterexample_in(
+        Path::new(CORPUS_DIR),
+        solver_id,
+        seed,
+        iterations,
+        gen,
+        fast,
+        brute,
+    )
+}
+
+/// Same as `find_counterexample` with the corpus under `root`.
+pub fn find_counterexample_in(
+    root: &Path,
+    solver_id: &str,
+    seed: u64,
+    iterations: usize,
+    mut gen: impl FnMut(&mut XorShift64) -> String,
+    fast: impl Fn(&str) -> String,
+    brute: impl Fn(&str) -> String,
+) -> io::Result<Option<String>> {
+    let mut rng = XorShift64::new(seed);
+    for _ in 0..iterations {
+        let input = gen(&mut rng);
+        if fast(&input) != brute(&input) {
+            let dir = root.join(solver_id);
+            fs::create_dir_all(&dir)?;
+            let mut hasher = DefaultHasher::new();
+            input.hash(&mut hasher);
+            fs::write(dir.join(format!("{:016x}.txt", hasher.finish())), &input)?;
+            return Ok(Some(input));
+        }
+    }
+    Ok(None)
+}
+
+/// Check `fast` against `brute` on every saved input of `solver_id`, returning how many were replayed.
+/// Nothing is replayed if the corpus does not exist.
+pub fn replay_corpus(
+    solver_id: &str,
+    fast: impl Fn(&str) -> String,
+    brute: impl Fn(&str) -> String,
+) -> usize {
+    replay_corpus_in(Path::new(CORPUS_DIR), solver_id, fast, brute)
+}
+
+/// Same as `replay_corpus` with the corpus under `root`.
+pub fn replay_corpus_in(
+    root: &Path,
+    solver_id: &str,
+    fast: impl Fn(&str) -> String,
+    brute: impl Fn(&str) -> String,
+) -> usize {
+    let Ok(entries) = fs::read_dir(root.join(solver_id)) else {
+        return 0;
+    };
+    let mut paths = entries
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()
+        .unwrap();
+    // sorted so failures are reported in the same order on every run
+    paths.sort();
+    for path in &paths {
+        let input = fs::read_to_string(path).unwrap();
+        assert_eq!(
+            fast(&input),
+            brute(&input),
+            "{} fails on {}",
+            solver_id,
+            path.display()
+        );
+    }
+    paths.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cio::{Scanner, Usize1};
+    use crate::union_find::UnionFind;
+
+    fn is_spanning_connected(n: usize, edges: &[(usize, usize)]) -> bool {
+        let mut uf = UnionFind::new(n);
+        edges.iter().for_each(|&(u, v)| {
+            uf.union(u, v);
+        });
+        n == 0 || uf.size(0) == n
+    }
+
+    #[test]
+    fn arrays_and_strings() {
+        let mut rng = XorShift64::new(43);
+        for _ in 0..100 {
+            let n = rng.gen_index(20);
+            let (text, a) = gen_array(&mut rng, n, -5..5);
+            assert!(a.iter().all(|x| (-5..5).contains(x)));
+            let mut scanner = Scanner::from(text.as_str());
+            let len = scanner.scan::<usize>();
+            assert_eq!(scanner.collect::<i64>(len), a);
+
+            let (text, s) = gen_string(&mut rng, n + 1, &['a', 'b']);
+            assert!(s.len() == n + 1 && s.chars().all(|c| c == 'a' || c == 'b'));
+            assert_eq!(text, format!("{}\n", s));
+        }
+        let (_, extreme) = gen_array(&mut rng, 100, i64::MIN..i64::MAX);
+        assert_eq!(extreme.len(), 100);
+    }
+
+    #[test]
+    fn trees_are_connected() {
+        let mut rng = XorShift64::new(4343);
+        for _ in 0..200 {
+            let n = 1 + rng.gen_index(30);
+            let one_indexed = rng.gen_index(2) == 1;
+            let (text, edges) = gen_tree_edges(&mut rng, n, one_indexed);
+            assert_eq!(edges.len(), n - 1);
+            assert!(is_spanning_connected(n, &edges));
+
+            let mut scanner = Scanner::from(text.as_str());
+            assert_eq!(scanner.scan::<usize>(), n);
+            let parsed = if one_indexed {
+                scanner.scan_edges(n - 1)
+            } else {
+                (0..n - 1).map(|_| scanner.tuple_2()).collect()
+            };
+            assert_eq!(parsed, edges);
+        }
+    }
+
+    #[test]
+    fn graphs_are_connected_and_simple() {
+        let mut rng = XorShift64::new(434343);
+        for _ in 0..200 {
+            let n = 1 + rng.gen_index(15);
+            let m = n - 1 + rng.gen_index(n * (n - 1) / 2 - (n - 1) + 1);
+            let (text, edges) = gen_connected_graph(&mut rng, n, m);
+            assert_eq!(edges.len(), m);
+            assert!(is_spanning_connected(n, &edges));
+            assert!(edges.iter().all(|&(u, v)| u != v));
+            let distinct = edges
+                .iter()
+                .map(|&(u, v)| (u.min(v), u.max(v)))
+                .collect::<HashSet<_>>();
+            assert_eq!(distinct.len(), m);
+
+            let mut scanner = Scanner::from(text.as_str());
+            assert_eq!(scanner.tuple_2::<usize, usize>(), (n, m));
+            let first = scanner.try_tuple_2::<Usize1, Usize1>().ok();
+            assert_eq!(first.map(|(u, v)| (u.0, v.0)), edges.first().copied());
+        }
+    }
+
+    fn sum_brute(input: &str) -> String {
+        let mut scanner = Scanner::from(input);
+        let n = scanner.scan::<usize>();
+        scanner.collect::<i64>(n).iter().sum::<i64>().to_string()
+    }
+
+    /// Planted bug: drops the last element of long arrays.
+    fn sum_buggy(input: &str) -> String {
+        let mut scanner = Scanner::from(input);
+        let n = scanner.scan::<usize>();
+        let a = scanner.collect::<i64>(n);
+        let len = if n > 5 { n - 1 } else { n };
+        a[..len].iter().sum::<i64>().to_string()
+    }
+
+    #[test]
+    fn corpus_round_trip() {
+        let root = std::env::temp_dir().join(format!("atcoder-stress-{}", std::process::id()));
+        assert_eq!(replay_corpus_in(&root, "sum", sum_buggy, sum_brute), 0);
+
+        let gen = |rng: &mut XorShift64| {
+            let n = rng.gen_index(10);
+            gen_array(rng, n, 1..100).0
+        };
+        let found = find_counterexample_in(&root, "sum", 45, 1000, gen, sum_buggy, sum_brute)
+            .unwrap()
+            .unwrap();
+        let files = fs::read_dir(root.join("sum")).unwrap().count();
+        assert_eq!(files, 1);
+        let replayed =
+            std::panic::catch_unwind(|| replay_corpus_in(&root, "sum", sum_buggy, sum_brute));
+        assert!(replayed.is_err());
+
+        // after the fix the saved case passes, and no new case is found
+        assert_eq!(replay_corpus_in(&root, "sum", sum_brute, sum_brute), 1);
+        let again = find_counterexample_in(&root, "sum", 45, 1000, gen, sum_brute, sum_brute);
+        assert_eq!(again.unwrap(), None);
+        assert!(sum_buggy(&found) != sum_brute(&found));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn grids_use_the_charset() {
+        let mut rng = XorShift64::new(43434343);
+        for _ in 0..100 {
+            let (h, w) = (1 + rng.gen_index(10), 1 + rng.gen_index(10));
+            let (text, grid) = gen_grid(&mut rng, h, w, &['.', '#']);
+            assert_eq!(grid.len(), h);
+            assert!(grid
+                .iter()
+                .all(|row| row.len() == w && row.iter().all(|&c| c == '.' || c == '#')));
+            let mut scanner = Scanner::from(text.as_str());
+            scanner.tuple_2::<usize, usize>();
+            assert_eq!(scanner.scan_char_grid(h), grid);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Classic sequence DPs.
+
+/// Length of the longest common subsequence of `a` and `b`, in `O(|a||b|)`.
+pub fn lcs_length<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut dp = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            dp[i + 1][j + 1] = if x == y {
+                dp[i][j] + 1
+            } else {
+                dp[i][j + 1].max(dp[i + 1][j])
+            };
+        }
+    }
+    dp[a.len()][b.len()]
+}
+
+/// Levenshtein distance: the minimum number of insertions, deletions and substitutions turning `a` into `b`.
+pub fn edit_distance<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut dp = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            let substitute = dp[i][j] + usize::from(x != y);
+            dp[i + 1][j + 1] = substitute.min(dp[i][j + 1] + 1).min(dp[i + 1][j] + 1);
+        }
+    }
+    dp[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lcs() {
+        assert_eq!(lcs_length(b"abcde", b"ace"), 3);
+        assert_eq!(lcs_length(b"abc", b"def"), 0);
+        assert_eq!(lcs_length(b"", b"abc"), 0);
+        assert_eq!(lcs_length(&[1, 3, 2, 4], &[3, 4, 1, 2]), 2);
+    }
+
+    #[test]
+    fn edit() {
+        assert_eq!(edit_distance(b"kitten", b"sitting"), 3);
+        assert_eq!(edit_distance(b"", b"abc"), 3);
+        assert_eq!(edit_distance(b"flaw", b"lawn"), 2);
+        assert_eq!(edit_distance(b"same", b"same"), 0);
+    }
+}
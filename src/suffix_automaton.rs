@@ -0,0 +1,218 @@
+//! Suffix automaton: the minimal automaton accepting every substring of a string.
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+struct State {
+    len: usize,
+    /// Suffix link, `None` only for the initial state.
+    link: Option<usize>,
+    next: BTreeMap<u8, usize>,
+    /// Size of the endpos set, i.e. how many times the substrings of this state occur.
+    occurrences: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    len: usize,
+}
+
+impl SuffixAutomaton {
+    pub fn build(s: &[u8]) -> Self {
+        let mut sa = Self {
+            states: Vec::with_capacity(2 * s.len() + 1),
+            len: s.len(),
+        };
+        sa.states.push(State {
+            len: 0,
+            link: None,
+            next: BTreeMap::new(),
+            occurrences: 0,
+        });
+        let mut last = 0;
+        for &c in s {
+            last = sa.extend(last, c);
+        }
+
+        // every prefix ends at its own state; clones inherit counts through suffix links
+        let mut order = (1..sa.states.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&v| std::cmp::Reverse(sa.states[v].len));
+        for v in order {
+            if let Some(link) = sa.states[v].link {
+                sa.states[link].occurrences += sa.states[v].occurrences;
+            }
+        }
+        sa
+    }
+
+    fn extend(&mut self, last: usize, c: u8) -> usize {
+        let cur = self.states.len();
+        self.states.push(State {
+            len: self.states[last].len + 1,
+            link: None,
+            next: BTreeMap::new(),
+            occurrences: 1,
+        });
+        let mut p = Some(last);
+        while let Some(v) = p {
+            if self.states[v].next.contains_key(&c) {
+                break;
+            }
+            self.states[v].next.insert(c, cur);
+            p = self.states[v].link;
+        }
+        let Some(p) = p else {
+            self.states[cur].link = Some(0);
+            return cur;
+        };
+        let q = self.states[p].next[&c];
+        if self.states[p].len + 1 == self.states[q].len {
+            self.states[cur].link = Some(q);
+            return cur;
+        }
+        let clone = self.states.len();
+        self.states.push(State {
+            len: self.states[p].len + 1,
+            link: self.states[q].link,
+            next: self.states[q].next.clone(),
+            occurrences: 0,
+        });
+        let mut p = Some(p);
+        while let Some(v) = p {
+            if self.states[v].next.get(&c) != Some(&q) {
+                break;
+            }
+            self.states[v].next.insert(c, clone);
+            p = self.states[v].link;
+        }
+        self.states[q].link = Some(clone);
+        self.states[cur].link = Some(clone);
+        cur
+    }
+
+    /// State reached by reading `pattern` from the initial state.
+    fn walk(&self, pattern: &[u8]) -> Option<usize> {
+        pattern
+            .iter()
+            .try_fold(0, |v, c| self.states[v].next.get(c).copied())
+    }
+
+    pub fn contains(&self, pattern: &[u8]) -> bool {
+        self.walk(pattern).is_some()
+    }
+
+    /// Number of distinct non-empty substrings.
+    pub fn count_distinct_substrings(&self) -> u64 {
+        self.states
+            .iter()
+            .skip(1)
+            .map(|st| (st.len - self.states[st.link.unwrap()].len) as u64)
+            .sum()
+    }
+
+    /// Number of (possibly overlapping) occurrences of `pattern`.
+    /// The empty pattern occurs at each of the `len + 1` positions.
+    pub fn count_occurrences(&self, pattern: &[u8]) -> u64 {
+        if pattern.is_empty() {
+            return self.len as u64 + 1;
+        }
+        self.walk(pattern).map_or(0, |v| self.states[v].occurrences)
+    }
+
+    /// Length of the longest string that is a substring of both the built string and `other`.
+    pub fn longest_common_substring(&self, other: &[u8]) -> usize {
+        let (mut v, mut len, mut best) = (0, 0, 0);
+        for c in other {
+            while v != 0 && !self.states[v].next.contains_key(c) {
+                v = self.states[v].link.unwrap();
+                len = self.states[v].len;
+            }
+            if let Some(&u) = self.states[v].next.get(c) {
+                v = u;
+                len += 1;
+            }
+            best = best.max(len);
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+    use std::collections::HashSet;
+
+    fn random_string(rng: &mut XorShift64, n: usize, alphabet: u8) -> Vec<u8> {
+        (0..n)
+            .map(|_| b'a' + rng.gen_range(0..alphabet as u64) as u8)
+            .collect()
+    }
+
+    fn occurrences(s: &[u8], p: &[u8]) -> u64 {
+        if p.len() > s.len() {
+            return 0;
+        }
+        s.windows(p.len()).filter(|w| *w == p).count() as u64
+    }
+
+    fn distinct(s: &[u8]) -> u64 {
+        let mut set = HashSet::new();
+        for i in 0..s.len() {
+            for j in i + 1..=s.len() {
+                set.insert(&s[i..j]);
+            }
+        }
+        set.len() as u64
+    }
+
+    fn lcs(a: &[u8], b: &[u8]) -> usize {
+        let mut best = 0;
+        let mut dp = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for i in 0..a.len() {
+            for j in 0..b.len() {
+                if a[i] == b[j] {
+                    dp[i + 1][j + 1] = dp[i][j] + 1;
+                    best = best.max(dp[i + 1][j + 1]);
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = XorShift64::new(17);
+        for _ in 0..30 {
+            let alphabet = 1 + rng.gen_range(0..4) as u8;
+            let n = rng.gen_index(201);
+            let s = random_string(&mut rng, n, alphabet);
+            let sa = SuffixAutomaton::build(&s);
+            assert_eq!(sa.count_distinct_substrings(), distinct(&s));
+            for _ in 0..20 {
+                let m = 1 + rng.gen_index(5);
+                let p = random_string(&mut rng, m, alphabet);
+                let count = occurrences(&s, &p);
+                assert_eq!(sa.contains(&p), count > 0);
+                assert_eq!(sa.count_occurrences(&p), count);
+            }
+            let m = rng.gen_index(201);
+            let t = random_string(&mut rng, m, alphabet);
+            assert_eq!(sa.longest_common_substring(&t), lcs(&s, &t));
+        }
+    }
+
+    #[test]
+    fn same_character() {
+        let s = vec![b'a'; 100];
+        let sa = SuffixAutomaton::build(&s);
+        assert_eq!(sa.count_distinct_substrings(), 100);
+        assert_eq!(sa.count_occurrences(b"a"), 100);
+        assert_eq!(sa.count_occurrences(&[b'a'; 60]), 41);
+        assert!(!sa.contains(&[b'a'; 101]));
+        assert!(!sa.contains(b"b"));
+        assert_eq!(sa.longest_common_substring(b"baaab"), 3);
+        assert_eq!(sa.count_occurrences(b""), 101);
+    }
+}
@@ -0,0 +1,172 @@
+//! Algorithms on trees given as adjacency lists.
+
+use crate::random::XorShift64;
+
+/// Preorder of the tree rooted at `root` together with the parent of each vertex.
+fn preorder(tree: &[Vec<usize>], root: usize) -> (Vec<usize>, Vec<usize>) {
+    let n = tree.len();
+    let mut order = Vec::with_capacity(n);
+    let mut parent = vec![usize::MAX; n];
+    let mut stack = vec![root];
+    parent[root] = root;
+    while let Some(v) = stack.pop() {
+        order.push(v);
+        for &u in &tree[v] {
+            if parent[u] == usize::MAX {
+                parent[u] = v;
+                stack.push(u);
+            }
+        }
+    }
+    (order, parent)
+}
+
+/// Keys of the random mapping applied to child hashes.
+/// They are fixed so that hashes computed by separate calls are comparable.
+fn hash_keys() -> (u64, u64) {
+    let mut rng = XorShift64::new(0x5eed_7ee5_4a54_1234);
+    (rng.next_u64() | 1, rng.next_u64())
+}
+
+fn mix(mut x: u64) -> u64 {
+    // splitmix64 finalizer
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Hash of the subtree rooted at each vertex when `tree` is rooted at `root`.
+/// Two subtrees get the same hash iff they are isomorphic as rooted trees (with high probability).
+pub fn rooted_tree_hash(tree: &[Vec<usize>], root: usize) -> Vec<u64> {
+    let (base, key) = hash_keys();
+    let (order, parent) = preorder(tree, root);
+    let mut hash = vec![0; tree.len()];
+    let mut children = Vec::new();
+    for &v in order.iter().rev() {
+        children.clear();
+        children.extend(
+            tree[v]
+                .iter()
+                .filter(|&&u| u != root && parent[u] == v)
+                .map(|&u| hash[u]),
+        );
+        children.sort_unstable();
+        let acc = children.iter().fold(key, |acc, &h| {
+            acc.wrapping_mul(base).wrapping_add(mix(h ^ key))
+        });
+        hash[v] = mix(acc.wrapping_add(children.len() as u64));
+    }
+    hash
+}
+
+/// One or two centroids of a connected tree.
+pub fn centroids(tree: &[Vec<usize>]) -> Vec<usize> {
+    let n = tree.len();
+    assert!(n > 0, "empty tree");
+    let (order, parent) = preorder(tree, 0);
+    let mut size = vec![1; n];
+    for &v in order.iter().rev() {
+        if v != 0 {
+            size[parent[v]] += size[v];
+        }
+    }
+    (0..n)
+        .filter(|&v| {
+            let largest_child = tree[v]
+                .iter()
+                .filter(|&&u| u != 0 && parent[u] == v)
+                .map(|&u| size[u])
+                .max()
+                .unwrap_or(0);
+            largest_child.max(n - size[v]) * 2 <= n
+        })
+        .collect()
+}
+
+/// Hash of an unrooted tree. Isomorphic trees hash equal regardless of labeling.
+/// The tree is rooted at its centroid, taking the smaller hash when there are two.
+pub fn unrooted_tree_hash(tree: &[Vec<usize>]) -> u64 {
+    centroids(tree)
+        .into_iter()
+        .map(|c| rooted_tree_hash(tree, c)[c])
+        .min()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn from_edges(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut tree = vec![Vec::new(); n];
+        for &(a, b) in edges {
+            tree[a].push(b);
+            tree[b].push(a);
+        }
+        tree
+    }
+
+    fn relabel(n: usize, edges: &[(usize, usize)], rng: &mut XorShift64) -> Vec<Vec<usize>> {
+        let mut perm = (0..n).collect::<Vec<_>>();
+        rng.shuffle(&mut perm);
+        let mut edges = edges
+            .iter()
+            .map(|&(a, b)| (perm[a], perm[b]))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut edges);
+        from_edges(n, &edges)
+    }
+
+    #[test]
+    fn isomorphic_relabelings_hash_equal() {
+        let mut rng = XorShift64::new(7);
+        for _ in 0..50 {
+            let n = 1 + rng.gen_index(30);
+            let edges = (1..n).map(|v| (rng.gen_index(v), v)).collect::<Vec<_>>();
+            let expected = unrooted_tree_hash(&from_edges(n, &edges));
+            for _ in 0..5 {
+                assert_eq!(unrooted_tree_hash(&relabel(n, &edges, &mut rng)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn non_isomorphic_trees_hash_different() {
+        // path, star and a spider on five vertices
+        let path = from_edges(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        let star = from_edges(5, &[(0, 1), (0, 2), (0, 3), (0, 4)]);
+        let spider = from_edges(5, &[(0, 1), (0, 2), (0, 3), (3, 4)]);
+        let hashes = [path, star, spider].map(|t| unrooted_tree_hash(&t));
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+        assert_ne!(hashes[1], hashes[2]);
+    }
+
+    #[test]
+    fn rooted_hash_distinguishes_roots() {
+        let path = from_edges(3, &[(0, 1), (1, 2)]);
+        let end = rooted_tree_hash(&path, 0);
+        let middle = rooted_tree_hash(&path, 1);
+        assert_ne!(end[0], middle[1]);
+        // leaves are all the same shape
+        assert_eq!(end[2], middle[0]);
+        assert_eq!(middle[0], middle[2]);
+    }
+
+    #[test]
+    fn two_centroids() {
+        // path of even length has two centroids
+        let path = from_edges(4, &[(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(centroids(&path), vec![1, 2]);
+        let relabeled = from_edges(4, &[(3, 0), (0, 2), (2, 1)]);
+        assert_eq!(unrooted_tree_hash(&path), unrooted_tree_hash(&relabeled));
+
+        // two stars joined by their centers
+        let a = from_edges(6, &[(0, 1), (0, 2), (0, 3), (3, 4), (3, 5)]);
+        let b = from_edges(6, &[(5, 4), (5, 3), (5, 0), (0, 1), (0, 2)]);
+        assert_eq!(centroids(&a).len(), 2);
+        assert_eq!(unrooted_tree_hash(&a), unrooted_tree_hash(&b));
+        let path = from_edges(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)]);
+        assert_ne!(unrooted_tree_hash(&a), unrooted_tree_hash(&path));
+    }
+}
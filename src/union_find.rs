@@ -0,0 +1,185 @@
+//! Disjoint set union with union by size and path compression.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Merge the sets of `x` and `y`, returning false if they were already the same set.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        self.union_reporting(x, y).is_some()
+    }
+
+    /// Merge the sets of `x` and `y`, returning their sizes just before merging.
+    /// `size_x * size_y` is the number of pairs newly connected by this union.
+    pub fn union_reporting(&mut self, x: usize, y: usize) -> Option<(usize, usize)> {
+        let (rx, ry) = (self.find(x), self.find(y));
+        if rx == ry {
+            return None;
+        }
+        let sizes = (self.size[rx], self.size[ry]);
+        let (big, small) = if sizes.0 >= sizes.1 {
+            (rx, ry)
+        } else {
+            (ry, rx)
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        Some(sizes)
+    }
+
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Size of the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Members of each set keyed by its root.
+    pub fn groups(&mut self) -> HashMap<usize, Vec<usize>> {
+        let mut groups = HashMap::new();
+        for x in 0..self.len() {
+            let root = self.find(x);
+            groups.entry(root).or_insert_with(Vec::new).push(x);
+        }
+        groups
+    }
+
+    /// Dense component id of each element, numbered in order of their smallest element.
+    pub fn labels(&mut self) -> Vec<usize> {
+        let mut id_of_root = vec![usize::MAX; self.len()];
+        let mut next = 0;
+        (0..self.len())
+            .map(|x| {
+                let root = self.find(x);
+                if id_of_root[root] == usize::MAX {
+                    id_of_root[root] = next;
+                    next += 1;
+                }
+                id_of_root[root]
+            })
+            .collect()
+    }
+
+    /// Children of each element under the current parent links, so the roots are the elements no
+    /// list contains. Links reflect only the path compression done by `find` calls so far.
+    pub fn to_forest(&self) -> Vec<Vec<usize>> {
+        let mut children = vec![Vec::new(); self.len()];
+        for (x, &p) in self.parent.iter().enumerate() {
+            if p != x {
+                children[p].push(x);
+            }
+        }
+        children
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::XorShift64;
+
+    #[test]
+    fn union_and_find() {
+        let mut uf = UnionFind::new(5);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(3, 4));
+        assert!(!uf.union(1, 0));
+        assert!(uf.same(0, 1));
+        assert!(!uf.same(1, 3));
+        assert_eq!(uf.size(4), 2);
+        assert_eq!(uf.size(2), 1);
+        let mut groups = uf.groups().into_values().collect::<Vec<_>>();
+        groups.sort();
+        assert_eq!(groups, vec![vec![0, 1], vec![2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn dense_labels() {
+        let mut uf = UnionFind::new(5);
+        uf.union(4, 1);
+        uf.union(0, 2);
+        uf.union(2, 3);
+        assert_eq!(uf.labels(), vec![0, 1, 0, 0, 1]);
+        assert_eq!(UnionFind::new(3).labels(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn forest_of_parent_links() {
+        let mut uf = UnionFind::new(5);
+        uf.union(0, 1);
+        uf.union(2, 3);
+        uf.union(1, 3);
+        assert_eq!(
+            uf.to_forest(),
+            vec![vec![1, 2], vec![], vec![3], vec![], vec![]]
+        );
+        // compression moves 3 under the root
+        uf.find(3);
+        assert_eq!(
+            uf.to_forest(),
+            vec![vec![1, 2, 3], vec![], vec![], vec![], vec![]]
+        );
+    }
+
+    #[test]
+    fn reported_sizes_count_new_pairs() {
+        let mut rng = XorShift64::new(18);
+        let n = 30;
+        let mut uf = UnionFind::new(n);
+        let mut connected_pairs = 0;
+        for _ in 0..60 {
+            let (x, y) = (rng.gen_index(n), rng.gen_index(n));
+            let before = uf.clone();
+            match uf.union_reporting(x, y) {
+                Some((a, b)) => {
+                    let mut before = before;
+                    assert_eq!((a, b), (before.size(x), before.size(y)));
+                    connected_pairs += a * b;
+                }
+                None => assert!(uf.same(x, y)),
+            }
+            let expected = (0..n)
+                .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                .filter(|&(i, j)| uf.clone().same(i, j))
+                .count();
+            assert_eq!(connected_pairs, expected);
+        }
+    }
+}
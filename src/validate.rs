@@ -0,0 +1,155 @@
+//! Checks for answers of constructive problems, to run locally before submitting.
+//!
+//! The `_detailed` variants explain what is wrong.
+
+use std::collections::HashSet;
+
+pub fn is_permutation(p: &[usize]) -> bool {
+    is_permutation_detailed(p).is_ok()
+}
+
+/// `p` contains each of `0..p.len()` exactly once.
+pub fn is_permutation_detailed(p: &[usize]) -> Result<(), String> {
+    let mut seen = vec![None; p.len()];
+    for (i, &v) in p.iter().enumerate() {
+        if v >= p.len() {
+            return Err(format!("p[{}] = {} is out of range 0..{}", i, v, p.len()));
+        }
+        if let Some(j) = seen[v] {
+            return Err(format!("{} appears twice, at {} and {}", v, j, i));
+        }
+        seen[v] = Some(i);
+    }
+    Ok(())
+}
+
+pub fn is_valid_path(graph: &[Vec<usize>], path: &[usize]) -> bool {
+    is_valid_path_detailed(graph, path).is_ok()
+}
+
+/// Consecutive vertices of `path` are adjacent in the undirected `graph` and no edge is used twice.
+pub fn is_valid_path_detailed(graph: &[Vec<usize>], path: &[usize]) -> Result<(), String> {
+    if let Some((i, &v)) = path.iter().enumerate().find(|&(_, &v)| v >= graph.len()) {
+        return Err(format!("path[{}] = {} is not a vertex", i, v));
+    }
+    let mut used = HashSet::new();
+    for (i, w) in path.windows(2).enumerate() {
+        let (u, v) = (w[0], w[1]);
+        if !graph[u].contains(&v) {
+            return Err(format!("step {}: no edge {} - {}", i, u, v));
+        }
+        if !used.insert((u.min(v), u.max(v))) {
+            return Err(format!("step {}: edge {} - {} is used twice", i, u, v));
+        }
+    }
+    Ok(())
+}
+
+pub fn is_matching(pairs: &[(usize, usize)], n: usize) -> bool {
+    is_matching_detailed(pairs, n).is_ok()
+}
+
+/// `pairs` are disjoint pairs of distinct vertices in `0..n`.
+pub fn is_matching_detailed(pairs: &[(usize, usize)], n: usize) -> Result<(), String> {
+    let mut matched = vec![None; n];
+    for (i, &(a, b)) in pairs.iter().enumerate() {
+        if a >= n || b >= n {
+            return Err(format!(
+                "pair {} ({}, {}) is out of range 0..{}",
+                i, a, b, n
+            ));
+        }
+        if a == b {
+            return Err(format!("pair {} matches {} with itself", i, a));
+        }
+        for v in [a, b] {
+            if let Some(j) = matched[v] {
+                return Err(format!("{} is matched by pairs {} and {}", v, j, i));
+            }
+            matched[v] = Some(i);
+        }
+    }
+    Ok(())
+}
+
+pub fn check_grid_dimensions<T, R: AsRef<[T]>>(grid: &[R], h: usize, w: usize) -> bool {
+    check_grid_dimensions_detailed(grid, h, w).is_ok()
+}
+
+/// `grid` has `h` rows of `w` cells each.
+pub fn check_grid_dimensions_detailed<T, R: AsRef<[T]>>(
+    grid: &[R],
+    h: usize,
+    w: usize,
+) -> Result<(), String> {
+    if grid.len() != h {
+        return Err(format!("expected {} rows, got {}", h, grid.len()));
+    }
+    for (i, row) in grid.iter().enumerate() {
+        let len = row.as_ref().len();
+        if len != w {
+            return Err(format!("row {} has {} cells, expected {}", i, len, w));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn permutation() {
+        assert!(is_permutation(&[]));
+        assert!(is_permutation(&[2, 0, 1]));
+        assert_eq!(
+            is_permutation_detailed(&[2, 0, 2]),
+            Err("2 appears twice, at 0 and 2".to_owned())
+        );
+        assert!(!is_permutation(&[0, 3, 1]));
+    }
+
+    #[test]
+    fn path() {
+        // triangle 0 - 1 - 2 with a tail 2 - 3
+        let graph = vec![vec![1, 2], vec![0, 2], vec![0, 1, 3], vec![2]];
+        assert!(is_valid_path(&graph, &[3, 2, 0, 1, 2]));
+        assert!(is_valid_path(&graph, &[1]));
+        assert_eq!(
+            is_valid_path_detailed(&graph, &[0, 1, 2, 1]),
+            Err("step 2: edge 2 - 1 is used twice".to_owned())
+        );
+        assert_eq!(
+            is_valid_path_detailed(&graph, &[0, 3]),
+            Err("step 0: no edge 0 - 3".to_owned())
+        );
+        assert!(!is_valid_path(&graph, &[0, 4]));
+    }
+
+    #[test]
+    fn matching() {
+        assert!(is_matching(&[(0, 3), (2, 1)], 4));
+        assert!(is_matching(&[], 0));
+        assert_eq!(
+            is_matching_detailed(&[(0, 1), (1, 2)], 3),
+            Err("1 is matched by pairs 0 and 1".to_owned())
+        );
+        assert!(!is_matching(&[(1, 1)], 3));
+        assert!(!is_matching(&[(0, 3)], 3));
+    }
+
+    #[test]
+    fn grid_dimensions() {
+        let grid = vec!["..#".to_owned(), "#..".to_owned()];
+        assert!(check_grid_dimensions(&grid, 2, 3));
+        assert_eq!(
+            check_grid_dimensions_detailed(&grid, 3, 3),
+            Err("expected 3 rows, got 2".to_owned())
+        );
+        let grid = vec![vec!['.', '#'], vec!['.']];
+        assert_eq!(
+            check_grid_dimensions_detailed(&grid, 2, 2),
+            Err("row 1 has 1 cells, expected 2".to_owned())
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Peak accounting of `MemTracker`, in its own test binary so no other test allocates meanwhile.
+#![cfg(feature = "mem-track")]
+
+use atcoder::cio::MemTracker;
+
+/// Allowance for the few small allocations the test harness makes alongside.
+const SLACK: usize = 64 << 10;
+
+#[test]
+fn peak_of_known_allocations() {
+    let size = 64 << 20;
+
+    MemTracker::reset_peak();
+    let before = MemTracker::current_bytes();
+    let v = vec![1u8; size];
+    let peak = MemTracker::peak_bytes() - before;
+    assert!(size <= peak && peak <= size + SLACK, "peak {}", peak);
+    drop(v);
+    assert!(MemTracker::current_bytes() <= before + SLACK);
+
+    // growing in place adds only the difference, so doubling peaks at twice the size
+    MemTracker::reset_peak();
+    let before = MemTracker::current_bytes();
+    let mut v = Vec::<u8>::with_capacity(size);
+    v.reserve_exact(2 * size);
+    let peak = MemTracker::peak_bytes() - before;
+    assert!(
+        2 * size <= peak && peak <= 2 * size + SLACK,
+        "peak {}",
+        peak
+    );
+    v.shrink_to(size);
+    assert!(MemTracker::current_bytes() - before <= size + SLACK);
+}